@@ -0,0 +1,326 @@
+//! Constant evaluation of discriminant expressions.
+//!
+//! This module folds discriminant expressions to integer values at expansion
+//! time where possible. It understands:
+//! - Integer, byte and char literals
+//! - Arithmetic, bitwise and shift operators, checked against the type
+//! - Parentheses and `as` casts between integer types
+//! - `MIN`/`MAX` of primitive integer types
+//!
+//! Anything else (such as references to user constants) is opaque: evaluation
+//! returns `Ok(None)` and the value is left for the compiler to compute.
+
+use crate::repr_enum::IntType;
+use syn::{BinOp, Error, Expr, Lit, Result, UnOp};
+
+/// The type of a sub-expression, as far as the evaluator can tell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueType {
+    Int(IntType),
+    Char,
+}
+
+/// Evaluate `expr` as a constant of type `ty`.
+///
+/// # Returns
+/// - `Ok(Some(value))` if the expression was folded to a value
+/// - `Ok(None)` if the expression cannot be evaluated at expansion time
+/// - `Err(_)` if the expression is known to be invalid (e.g., it overflows `ty`)
+pub fn evaluate(expr: &Expr, ty: IntType) -> Result<Option<i128>> {
+    match expr {
+        Expr::Lit(lit_expr) => match &lit_expr.lit {
+            Lit::Int(lit_int) => {
+                if !lit_int.suffix().is_empty() && lit_int.suffix() != ty.name() {
+                    return Ok(None);
+                }
+                let Ok(value) = lit_int.base10_parse::<i128>() else {
+                    return Ok(None);
+                };
+                check_range(expr, value, ty, "literal out of range")
+            }
+            Lit::Byte(lit_byte) if ty == IntType::U8 => Ok(Some(i128::from(lit_byte.value()))),
+            _ => Ok(None),
+        },
+        Expr::Paren(paren) => evaluate(&paren.expr, ty),
+        Expr::Group(group) => evaluate(&group.expr, ty),
+        Expr::Unary(unary) => match &unary.op {
+            UnOp::Neg(_) if ty.is_signed() => {
+                // Negated literals are checked as a whole so that `-128` fits in i8
+                let value = match &*unary.expr {
+                    Expr::Lit(syn::ExprLit {
+                        lit: Lit::Int(lit_int),
+                        ..
+                    }) if lit_int.suffix().is_empty() || lit_int.suffix() == ty.name() => {
+                        lit_int.base10_parse::<i128>().ok()
+                    }
+                    inner => evaluate(inner, ty)?,
+                };
+                match value.and_then(i128::checked_neg) {
+                    Some(value) => check_range(expr, value, ty, "attempt to negate with overflow"),
+                    None => Ok(None),
+                }
+            }
+            UnOp::Not(_) => Ok(evaluate(&unary.expr, ty)?.and_then(|value| tracked(ty.wrap(!value), ty))),
+            _ => Ok(None),
+        },
+        Expr::Binary(binary) => evaluate_binary(binary, ty),
+        Expr::Cast(cast) => {
+            if type_of_path(&cast.ty) != Some(ty) {
+                return Ok(None);
+            }
+            let value = match infer_type(&cast.expr) {
+                Some(ValueType::Int(inner_ty)) => evaluate(&cast.expr, inner_ty)?,
+                Some(ValueType::Char) => evaluate_char(&cast.expr),
+                // An unsuffixed literal takes the type it is cast to; other
+                // unconstrained operands are left to the compiler
+                None if is_int_literal(&cast.expr) => evaluate(&cast.expr, ty)?,
+                None => None,
+            };
+            Ok(value.and_then(|value| tracked(ty.wrap(value), ty)))
+        }
+        Expr::Path(path) => {
+            let segments = &path.path.segments;
+            if path.qself.is_some() || segments.len() != 2 {
+                return Ok(None);
+            }
+            if IntType::from_ident(&segments[0].ident) != Some(ty) {
+                return Ok(None);
+            }
+            let constant = &segments[1].ident;
+            if constant == "MIN" {
+                Ok(Some(ty.min_value()))
+            } else if constant == "MAX" && ty != IntType::U128 {
+                Ok(Some(ty.max_value()))
+            } else {
+                Ok(None)
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Evaluate a binary operation, checking the result against `ty`.
+fn evaluate_binary(binary: &syn::ExprBinary, ty: IntType) -> Result<Option<i128>> {
+    let expr = Expr::Binary(binary.clone());
+    let Some(lhs) = evaluate(&binary.left, ty)? else {
+        return Ok(None);
+    };
+
+    // The right-hand side of a shift has its own type
+    if let BinOp::Shl(_) | BinOp::Shr(_) = binary.op {
+        let rhs = match infer_type(&binary.right).unwrap_or(ValueType::Int(IntType::I32)) {
+            ValueType::Int(rhs_ty) => evaluate(&binary.right, rhs_ty)?,
+            ValueType::Char => None,
+        };
+        let Some(rhs) = rhs else {
+            return Ok(None);
+        };
+        if !(0..i128::from(ty.bits())).contains(&rhs) {
+            return Err(Error::new_spanned(expr, "attempt to shift with overflow"));
+        }
+        let value = match binary.op {
+            BinOp::Shl(_) => ty.wrap(lhs.wrapping_shl(rhs as u32)),
+            _ => lhs >> rhs,
+        };
+        return Ok(tracked(value, ty));
+    }
+
+    let Some(rhs) = evaluate(&binary.right, ty)? else {
+        return Ok(None);
+    };
+    let (value, message) = match binary.op {
+        BinOp::Add(_) => (lhs.checked_add(rhs), "attempt to add with overflow"),
+        BinOp::Sub(_) => (lhs.checked_sub(rhs), "attempt to subtract with overflow"),
+        BinOp::Mul(_) => (lhs.checked_mul(rhs), "attempt to multiply with overflow"),
+        BinOp::Div(_) if rhs == 0 => (None, "attempt to divide by zero"),
+        BinOp::Div(_) => (lhs.checked_div(rhs), "attempt to divide with overflow"),
+        BinOp::Rem(_) if rhs == 0 => (None, "attempt to calculate the remainder with a divisor of zero"),
+        BinOp::Rem(_) => (lhs.checked_rem(rhs), "attempt to calculate the remainder with overflow"),
+        BinOp::BitAnd(_) => (Some(lhs & rhs), ""),
+        BinOp::BitOr(_) => (Some(lhs | rhs), ""),
+        BinOp::BitXor(_) => (Some(lhs ^ rhs), ""),
+        _ => return Ok(None),
+    };
+    match value {
+        Some(value) => check_range(&expr, value, ty, message),
+        // Sums and products of `u128` values may be above `i128::MAX`, and are
+        // left to the compiler
+        None if ty == IntType::U128 && matches!(binary.op, BinOp::Add(_) | BinOp::Mul(_)) => Ok(None),
+        None => Err(Error::new_spanned(expr, message)),
+    }
+}
+
+/// `value` if it is a value of `ty`. The wrapping operations on `u128` give
+/// negative results for values above `i128::MAX`, which cannot be tracked.
+fn tracked(value: i128, ty: IntType) -> Option<i128> {
+    ty.contains(value).then_some(value)
+}
+
+/// Evaluate a char literal (possibly parenthesized) to its code point.
+fn evaluate_char(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Char(lit_char),
+            ..
+        }) => Some(i128::from(u32::from(lit_char.value()))),
+        Expr::Paren(paren) => evaluate_char(&paren.expr),
+        Expr::Group(group) => evaluate_char(&group.expr),
+        _ => None,
+    }
+}
+
+/// Ensure `value` fits in `ty`, reporting `message` on `expr` otherwise.
+fn check_range(expr: &Expr, value: i128, ty: IntType, message: &str) -> Result<Option<i128>> {
+    if ty.contains(value) {
+        Ok(Some(value))
+    } else {
+        Err(Error::new_spanned(
+            expr,
+            format!("{} for `{}`", message, ty.name()),
+        ))
+    }
+}
+
+/// Infer the type of a sub-expression that is not constrained by its context,
+/// such as the operand of a cast, from its suffixes, casts and paths. Returns
+/// `None` if nothing in the expression fixes its type, as for unsuffixed
+/// literals, which the compiler types from the context or defaults to `i32`.
+fn infer_type(expr: &Expr) -> Option<ValueType> {
+    match expr {
+        Expr::Lit(lit_expr) => match &lit_expr.lit {
+            Lit::Int(lit_int) => int_type_from_name(lit_int.suffix()).map(ValueType::Int),
+            Lit::Byte(_) => Some(ValueType::Int(IntType::U8)),
+            Lit::Char(_) => Some(ValueType::Char),
+            _ => None,
+        },
+        Expr::Paren(paren) => infer_type(&paren.expr),
+        Expr::Group(group) => infer_type(&group.expr),
+        Expr::Unary(unary) => infer_type(&unary.expr),
+        Expr::Binary(binary) => infer_type(&binary.left).or_else(|| infer_type(&binary.right)),
+        Expr::Cast(cast) => type_of_path(&cast.ty).map(ValueType::Int),
+        Expr::Path(path) => path
+            .path
+            .segments
+            .first()
+            .and_then(|segment| IntType::from_ident(&segment.ident))
+            .map(ValueType::Int),
+        _ => None,
+    }
+}
+
+/// Whether `expr` is an integer literal, possibly parenthesized.
+fn is_int_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(_), ..
+        }) => true,
+        Expr::Paren(paren) => is_int_literal(&paren.expr),
+        Expr::Group(group) => is_int_literal(&group.expr),
+        _ => false,
+    }
+}
+
+/// The integer type named by a type like `u8`, if any.
+fn type_of_path(ty: &syn::Type) -> Option<IntType> {
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => {
+            path.path.get_ident().and_then(IntType::from_ident)
+        }
+        syn::Type::Paren(paren) => type_of_path(&paren.elem),
+        syn::Type::Group(group) => type_of_path(&group.elem),
+        _ => None,
+    }
+}
+
+/// The integer type with the given name, if any.
+fn int_type_from_name(name: &str) -> Option<IntType> {
+    IntType::ALL.into_iter().find(|ty| ty.name() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn eval(expr: Expr, ty: IntType) -> Option<i128> {
+        evaluate(&expr, ty).unwrap()
+    }
+
+    #[test]
+    fn test_evaluate_literals() {
+        assert_eq!(eval(parse_quote! { 42 }, IntType::I32), Some(42));
+        assert_eq!(eval(parse_quote! { -10 }, IntType::I32), Some(-10));
+        assert_eq!(eval(parse_quote! { -128 }, IntType::I8), Some(-128));
+        assert_eq!(eval(parse_quote! { 0x10 }, IntType::U8), Some(16));
+        assert_eq!(eval(parse_quote! { 7u8 }, IntType::U8), Some(7));
+        assert_eq!(eval(parse_quote! { b'A' }, IntType::U8), Some(65));
+    }
+
+    #[test]
+    fn test_evaluate_operators() {
+        assert_eq!(eval(parse_quote! { 1 + 2 }, IntType::U8), Some(3));
+        assert_eq!(eval(parse_quote! { 1 << 4 }, IntType::U8), Some(16));
+        assert_eq!(eval(parse_quote! { 0x10 | 0x01 }, IntType::U8), Some(17));
+        assert_eq!(eval(parse_quote! { (3 * 8) }, IntType::U8), Some(24));
+        assert_eq!(eval(parse_quote! { 0xFF & !0x0F }, IntType::U8), Some(0xF0));
+        assert_eq!(eval(parse_quote! { 100 / 7 % 5 }, IntType::U8), Some(4));
+        assert_eq!(eval(parse_quote! { -16 >> 2 }, IntType::I8), Some(-4));
+        assert_eq!(eval(parse_quote! { 0xF0 << 4 }, IntType::U16), Some(0xF00));
+    }
+
+    #[test]
+    fn test_evaluate_casts_and_paths() {
+        assert_eq!(eval(parse_quote! { 'a' as u8 }, IntType::U8), Some(97));
+        assert_eq!(eval(parse_quote! { 300u16 as u8 }, IntType::U8), Some(44));
+        assert_eq!(eval(parse_quote! { 0xFFFF_FFFF as u32 }, IntType::U32), Some(0xFFFF_FFFF));
+        assert_eq!(eval(parse_quote! { (0x80) as u8 }, IntType::U8), Some(0x80));
+        assert_eq!(eval(parse_quote! { -1i8 as u8 }, IntType::U8), Some(255));
+        assert_eq!(eval(parse_quote! { u8::MAX - 1 }, IntType::U8), Some(254));
+        assert_eq!(eval(parse_quote! { i16::MIN }, IntType::I16), Some(-32768));
+        assert_eq!(eval(parse_quote! { u8::MAX as u16 + 1 }, IntType::U16), Some(256));
+        assert_eq!(eval(parse_quote! { i128::MAX }, IntType::I128), Some(i128::MAX));
+        assert_eq!(eval(parse_quote! { i128::MIN }, IntType::I128), Some(i128::MIN));
+    }
+
+    #[test]
+    fn test_evaluate_u128_beyond_i128() {
+        assert_eq!(eval(parse_quote! { u128::MAX }, IntType::U128), None);
+        assert_eq!(eval(parse_quote! { !0 }, IntType::U128), None);
+        assert_eq!(eval(parse_quote! { -1i8 as u128 }, IntType::U128), None);
+        assert_eq!(eval(parse_quote! { 1 << 127 }, IntType::U128), None);
+        assert_eq!(eval(parse_quote! { !0 ^ (1 << 127) }, IntType::U128), None);
+        assert_eq!(eval(parse_quote! { (1 << 126) - 1 }, IntType::U128), Some((1 << 126) - 1));
+        assert_eq!(eval(parse_quote! { i128::MAX as u128 + 1 }, IntType::U128), None);
+        assert_eq!(eval(parse_quote! { (1 << 100) * (1 << 30) }, IntType::U128), None);
+    }
+
+    #[test]
+    fn test_evaluate_opaque() {
+        assert_eq!(eval(parse_quote! { BASE }, IntType::U8), None);
+        assert_eq!(eval(parse_quote! { BASE + 1 }, IntType::U8), None);
+        assert_eq!(eval(parse_quote! { 1 + OFFSET }, IntType::U8), None);
+        assert_eq!(eval(parse_quote! { u16::MAX }, IntType::U8), None);
+        assert_eq!(eval(parse_quote! { offset() }, IntType::U8), None);
+        // The type of an unconstrained operand is left to the compiler
+        assert_eq!(eval(parse_quote! { (200 + 100) as u8 }, IntType::U8), None);
+        assert_eq!(eval(parse_quote! { -1 as u8 }, IntType::U8), None);
+    }
+
+    #[test]
+    fn test_evaluate_overflow() {
+        let exprs: [Expr; 6] = [
+            parse_quote! { 256 },
+            parse_quote! { 200 + 100 },
+            parse_quote! { 0 - 1 },
+            parse_quote! { 1 << 8 },
+            parse_quote! { u8::MAX + 1 },
+            parse_quote! { 1 / 0 },
+        ];
+        for expr in exprs {
+            assert!(evaluate(&expr, IntType::U8).is_err());
+        }
+        assert!(evaluate(&parse_quote! { -129 }, IntType::I8).is_err());
+        // An unsuffixed literal takes the type it is cast to
+        assert!(evaluate(&parse_quote! { 300 as u8 }, IntType::U8).is_err());
+    }
+}
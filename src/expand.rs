@@ -6,26 +6,342 @@
 //! - `From<Enum>` trait implementation
 //! - `TryFrom<T>` trait implementation
 //! - Error type definition
+//! - Conversions to and from additional integer types (`also`)
+//! - `NonZero` conversions for enums without a zero discriminant
+//! - Sentinel encoding of `Option<Enum>` (`none`)
+//! - Packing into larger words for bit-width-limited enums (`bits`)
+//! - Masked decoding that ignores reserved bits (`mask`)
+//! - Reserved value ranges with a distinct error kind (`reserved`)
+//! - A set type of single-bit variants (`flags`)
+//! - A bitset type indexed by variant ordinal (`set`)
+//! - An array type with an element per variant (`map`)
+//! - Bit-packed vector and array types (`packed`)
+//! - An atomic wrapper type (`atomic`)
+//! - An open newtype preserving unknown values, around a `{Name}Known` enum (`open`)
+//! - Char conversions for `ascii` enums
 
-use crate::repr_enum::ReprEnum;
-use quote::{format_ident, quote};
+use crate::parse::path_to_string;
+use crate::repr_enum::{Compose, EnumVariant, IntType, ReprEnum};
+use quote::{format_ident, quote, quote_spanned};
 
 /// Generate the complete expanded code for a repr_cast enum.
 ///
 /// This is the main codegen entry point that orchestrates all code generation.
 pub fn expand_repr_cast(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    if repr_enum.options.open {
+        return expand_open(repr_enum);
+    }
+    if repr_enum.variants.iter().any(EnumVariant::has_fields) {
+        return expand_tagged(repr_enum);
+    }
+
     let enum_def = generate_enum_definition(repr_enum);
     let impl_methods = generate_impl_methods(repr_enum);
+    let overlap_checks = generate_overlap_checks(repr_enum);
+    let variant_info = if repr_enum.options.variants {
+        generate_variant_info(repr_enum)
+    } else {
+        quote! {}
+    };
     let from_impl = generate_from_impl(repr_enum);
     let try_from_impl = generate_try_from_impl(repr_enum);
     let error_type = generate_error_type(repr_enum);
+    let also_impls = generate_also_impls(repr_enum);
+    let nonzero_impls = if repr_enum.options.nonzero {
+        generate_nonzero_impls(repr_enum)
+    } else {
+        quote! {}
+    };
+    let sentinel_impls = match &repr_enum.options.none {
+        Some(sentinel) => generate_sentinel_impls(repr_enum, sentinel),
+        None => quote! {},
+    };
+    let bits_impls = match &repr_enum.options.bits {
+        Some(bits) => generate_bits_impls(repr_enum, bits),
+        None => quote! {},
+    };
+    let mask_impls = match &repr_enum.options.mask {
+        Some(mask) => generate_mask_impls(repr_enum, mask),
+        None => quote! {},
+    };
+    let reserved_impls = if repr_enum.options.reserved.is_empty() {
+        quote! {}
+    } else {
+        generate_reserved_impls(repr_enum)
+    };
+    let flags_impls = if repr_enum.options.flags {
+        generate_flags_impls(repr_enum)
+    } else {
+        quote! {}
+    };
+    let set_impls = if repr_enum.options.set {
+        generate_set_impls(repr_enum)
+    } else {
+        quote! {}
+    };
+    let map_impls = if repr_enum.options.map {
+        generate_map_impls(repr_enum)
+    } else {
+        quote! {}
+    };
+    let packed_impls = if repr_enum.options.packed {
+        generate_packed_impls(repr_enum)
+    } else {
+        quote! {}
+    };
+    let atomic_impls = if repr_enum.options.atomic {
+        generate_atomic_impls(repr_enum)
+    } else {
+        quote! {}
+    };
+    let ascii_impls = if repr_enum.options.ascii {
+        generate_ascii_impls(repr_enum)
+    } else {
+        quote! {}
+    };
+    let codec_impls = if repr_enum.options.codec {
+        generate_codec_impls(repr_enum)
+    } else {
+        quote! {}
+    };
+    let compose_impls = match &repr_enum.options.compose {
+        Some(compose) => generate_compose_impls(repr_enum, compose),
+        None => quote! {},
+    };
 
     quote! {
         #enum_def
         #impl_methods
+        #overlap_checks
+        #variant_info
         #from_impl
         #try_from_impl
         #error_type
+        #also_impls
+        #nonzero_impls
+        #sentinel_impls
+        #bits_impls
+        #mask_impls
+        #reserved_impls
+        #flags_impls
+        #set_impls
+        #map_impls
+        #packed_impls
+        #atomic_impls
+        #ascii_impls
+        #codec_impls
+        #compose_impls
+    }
+}
+
+/// Generate an enum with fields: the enum itself keeps its fields and explicit
+/// discriminants under `#[repr(T)]`, and a fieldless `{Name}Tag` with the same
+/// variants and discriminants is expanded with every option:
+/// - `tag()`, the discriminant of the variant
+/// - `From<&{Name}> for {Name}Tag`
+fn expand_tagged(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let tag_name = format_ident!("{}Tag", name);
+    let allow_deprecated = allow_deprecated(repr_enum);
+    let repr_type = &repr_enum.repr_type;
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+
+    let mut tag = repr_enum.clone();
+    tag.name = tag_name.clone();
+    tag.generics = Default::default();
+    // The alias for `auto` is generated once, named after the enum
+    tag.options.auto = false;
+    tag.options.codec = false;
+    let doc = format!("The variants of [`{}`] without their fields.", name);
+    tag.attributes = vec![
+        syn::parse_quote! { #[doc = #doc] },
+        syn::parse_quote! { #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)] },
+    ];
+    for variant in &mut tag.variants {
+        variant.fields = syn::Fields::Unit;
+        variant.attributes = variant
+            .cfg
+            .iter()
+            .chain(
+                variant
+                    .attributes
+                    .iter()
+                    .filter(|attr| attr.path().is_ident("doc") || attr.path().is_ident("deprecated")),
+            )
+            .cloned()
+            .collect();
+    }
+    let tag_expanded = expand_repr_cast(&tag);
+
+    let arms = repr_enum.variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let cfg = &v.cfg;
+        quote! {
+            #(#cfg)*
+            Self::#variant_name { .. } => #tag_name::#variant_name,
+        }
+    });
+    let enum_def = generate_enum_definition(repr_enum);
+    let codec_impls = if repr_enum.options.codec {
+        generate_codec_impls(repr_enum)
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #enum_def
+
+        #allow_deprecated
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns the variant without its fields.
+            #[inline]
+            pub const fn kind(&self) -> #tag_name {
+                match self {
+                    #(#arms)*
+                }
+            }
+
+            /// Returns the discriminant of the variant.
+            #[inline]
+            pub const fn tag(&self) -> #repr_type {
+                self.kind() as #repr_type
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<&#name #ty_generics> for #tag_name #where_clause {
+            #[inline]
+            fn from(value: &#name #ty_generics) -> Self {
+                value.kind()
+            }
+        }
+
+        #tag_expanded
+        #codec_impls
+    }
+}
+
+/// Generate an `open` enum: the enum itself becomes `{Name}Known`, expanded with
+/// every other option, and `{Name}` becomes a newtype around the repr type that
+/// holds any value:
+/// - An associated const for every variant
+/// - `known()`, the variant of the value if there is one
+/// - `Debug` and `Display`, printing the variant name or `Unknown(value)`
+/// - Infallible conversions from and to the repr type and from `{Name}Known`
+fn expand_open(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let known_name = format_ident!("{}Known", name);
+    let allow_deprecated = allow_deprecated(repr_enum);
+    let repr_type = &repr_enum.repr_type;
+    let vis = &repr_enum.visibility;
+    let error_type_name = format_ident!("{}ConversionError", known_name);
+
+    let mut known = repr_enum.clone();
+    known.name = known_name.clone();
+    known.options.open = false;
+    let known_expanded = expand_repr_cast(&known);
+
+    let docs: Vec<_> = repr_enum
+        .attributes
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .collect();
+
+    let consts = repr_enum.variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let cfg = &v.cfg;
+        let attrs = v
+            .attributes
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc") || attr.path().is_ident("deprecated"));
+        quote! {
+            #(#cfg)*
+            #(#attrs)*
+            pub const #variant_name: Self = Self(#known_name::#variant_name as #repr_type);
+        }
+    });
+
+    quote! {
+        #known_expanded
+
+        #(#docs)*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(transparent)]
+        #vis struct #name(pub #repr_type);
+
+        #allow_deprecated
+        #[allow(non_upper_case_globals)]
+        impl #name {
+            #(#consts)*
+
+            /// Wraps an integer value, known or not.
+            #[inline]
+            pub const fn new(value: #repr_type) -> Self {
+                Self(value)
+            }
+
+            /// Returns the integer value.
+            #[inline]
+            pub const fn get(self) -> #repr_type {
+                self.0
+            }
+
+            /// Returns the known variant of the value, or `None` if it is unknown.
+            #[inline]
+            pub const fn known(self) -> ::core::option::Option<#known_name> {
+                #known_name::from_repr(self.0)
+            }
+
+            /// Returns `true` if the value is a known variant.
+            #[inline]
+            pub const fn is_known(self) -> bool {
+                self.known().is_some()
+            }
+        }
+
+        impl ::core::convert::From<#repr_type> for #name {
+            #[inline]
+            fn from(value: #repr_type) -> Self {
+                Self(value)
+            }
+        }
+
+        impl ::core::convert::From<#name> for #repr_type {
+            #[inline]
+            fn from(value: #name) -> Self {
+                value.0
+            }
+        }
+
+        impl ::core::convert::From<#known_name> for #name {
+            #[inline]
+            fn from(value: #known_name) -> Self {
+                Self(value.as_repr())
+            }
+        }
+
+        impl ::core::convert::TryFrom<#name> for #known_name {
+            type Error = #error_type_name;
+
+            #[inline]
+            fn try_from(value: #name) -> ::core::result::Result<Self, Self::Error> {
+                Self::try_from(value.0)
+            }
+        }
+
+        impl ::core::fmt::Debug for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self.known() {
+                    ::core::option::Option::Some(known) => f.write_str(known.name()),
+                    ::core::option::Option::None => write!(f, "Unknown({:?})", self.0),
+                }
+            }
+        }
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Debug::fmt(self, f)
+            }
+        }
     }
 }
 
@@ -39,162 +355,2747 @@ fn generate_enum_definition(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
     let variants = repr_enum.variants.iter().map(|v| {
         let variant_name = &v.name;
         let variant_attrs = &v.attributes;
+        let fields = &v.fields;
         if let Some(expr) = &v.discriminant {
             quote! {
                 #(#variant_attrs)*
-                #variant_name = #expr
+                #variant_name #fields = #expr
             }
         } else {
             quote! {
                 #(#variant_attrs)*
-                #variant_name
+                #variant_name #fields
             }
         }
     });
 
+    // The type picked by `auto` is named by an alias, as inherent associated
+    // types are not available
+    let repr_alias = if repr_enum.options.auto {
+        let alias = format_ident!("{}Repr", name);
+        let doc = format!("The repr type chosen for [`{}`] by `repr_cast`.", name);
+        quote! {
+            #[doc = #doc]
+            #vis type #alias = #repr_type;
+        }
+    } else {
+        quote! {}
+    };
+
+    let other_variant = other_arm(repr_enum, |other| {
+        let doc = format!("A value that doesn't match any other variant of [`{}`].", name);
+        let discriminant = other_discriminant(repr_enum).map(|value| {
+            let lit = proc_macro2::Literal::u128_unsuffixed(value.unsigned_abs());
+            if value < 0 {
+                quote! { = -#lit }
+            } else {
+                quote! { = #lit }
+            }
+        });
+        quote! { #[doc = #doc] #other(#repr_type) #discriminant, }
+    });
+
+    let generics = &repr_enum.generics;
+    let where_clause = &generics.where_clause;
+
     quote! {
         #(#attrs)*
         #[repr(#repr_type)]
-        #vis enum #name {
-            #(#variants),*
+        #vis enum #name #generics #where_clause {
+            #(#variants,)*
+            #other_variant
         }
+
+        #repr_alias
     }
 }
 
-/// Generate the impl block with from_repr and as_repr methods.
+/// Generate the impl block with from_repr and as_repr methods, and
+/// `from_repr_strict` with the `strict` option.
 fn generate_impl_methods(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
     let name = &repr_enum.name;
+    let allow_deprecated = allow_deprecated(repr_enum);
     let repr_type = &repr_enum.repr_type;
     let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
 
     // Generate if-else chain for from_repr to handle complex discriminant expressions
     // This works because we compare against the actual enum variant cast to the repr type,
     // which evaluates any expressions at compile time
-    let from_repr_checks = repr_enum.variants.iter().map(|v| {
+    let from_repr_checks = repr_enum.variants.iter().filter(|v| !v.options.skip).map(|v| {
+        let variant_name = &v.name;
+        let discriminant = variant_value(repr_enum, v);
+        let cfg = &v.cfg;
+        quote! {
+            #(#cfg)*
+            if value == #discriminant {
+                return ::core::option::Option::Some(#name::#variant_name);
+            };
+        }
+    });
+
+    // Aliases and ranges are checked after every discriminant
+    let accepted_checks = repr_enum.variants.iter().flat_map(|v| {
+        let variant_name = &v.name;
+        let cfg = &v.cfg;
+        v.options.accepts.iter().map(move |accepted| {
+            let condition = match (&accepted.start, &accepted.end) {
+                (Some(start), _) if accepted.is_alias() => quote! { value == (#start) },
+                (Some(start), Some(end)) => quote! { value >= (#start) && value <= (#end) },
+                (Some(start), None) => quote! { value >= (#start) },
+                (None, Some(end)) => quote! { value <= (#end) },
+                (None, None) => quote! { true },
+            };
+            quote! {
+                #(#cfg)*
+                if #condition {
+                    return ::core::option::Option::Some(#name::#variant_name);
+                };
+            }
+        })
+    });
+
+    let from_repr_strict = if repr_enum.options.strict {
+        quote! {
+            /// Converts an integer value to the enum variant, rejecting deprecated variants.
+            /// Returns `None` if the value doesn't match any variant or matches a deprecated one.
+            #[inline]
+            pub const fn from_repr_strict(value: #repr_type) -> ::core::option::Option<Self> {
+                match Self::from_repr(value) {
+                    ::core::option::Option::Some(variant) if variant.is_deprecated() => {
+                        ::core::option::Option::None
+                    }
+                    result => result,
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let other_arm = other_arm(repr_enum, |other| quote! { #name::#other(value) => *value, });
+    let as_repr_arms = repr_enum.variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let discriminant = variant_value(repr_enum, v);
+        let cfg = &v.cfg;
+        quote! {
+            #(#cfg)*
+            #name::#variant_name => #discriminant,
+        }
+    });
+
+    quote! {
+        #allow_deprecated
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Converts an integer value to the enum variant.
+            /// Returns `None` if the value doesn't match any variant.
+            #[inline]
+            pub const fn from_repr(value: #repr_type) -> ::core::option::Option<Self> {
+                #(#from_repr_checks)*
+                #(#accepted_checks)*
+                ::core::option::Option::None
+            }
+
+            #from_repr_strict
+
+            /// Converts the enum variant to its integer representation.
+            #[inline]
+            pub const fn as_repr(&self) -> #repr_type {
+                match self {
+                    #(#as_repr_arms)*
+                    #other_arm
+                }
+            }
+        }
+    }
+}
+
+/// Generate the variant list and names:
+/// - `COUNT` constant, the number of variants in `VARIANTS`
+/// - `VARIANTS` constant and `iter()` method, in declaration order without `hidden` variants
+/// - `name()` method, returning the variant name or its `rename`
+/// - `is_deprecated()` method
+/// - `ordinal()` and `from_ordinal()` methods, the position in declaration order
+fn generate_variant_info(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let allow_deprecated = allow_deprecated(repr_enum);
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+    let visible: Vec<_> = repr_enum.variants.iter().filter(|v| !v.options.hidden).collect();
+    let count = count_variants(visible.iter().copied());
+    let ordinal_count = count_variants(repr_enum.variants.iter());
+    let variants = if visible.iter().all(|v| v.cfg.is_empty()) {
+        let visible = visible.iter().map(|v| {
+            let variant_name = &v.name;
+            quote! { #name::#variant_name }
+        });
+        quote! { &[#(#visible),*] }
+    } else {
+        gated_variant_array(repr_enum, &visible)
+    };
+
+    let name_arms = repr_enum.variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let display_name = match &v.options.rename {
+            Some(rename) => rename.value(),
+            None => variant_name.to_string(),
+        };
+        let cfg = &v.cfg;
+        quote! {
+            #(#cfg)*
+            #name::#variant_name => #display_name,
+        }
+    });
+
+    // Ordinals count the enabled variants before each variant
+    let ordinals: Vec<_> = (0..repr_enum.variants.len())
+        .map(|i| count_variants(repr_enum.variants[..i].iter()))
+        .collect();
+    let ordinal_arms = repr_enum.variants.iter().zip(&ordinals).map(|(v, ordinal)| {
+        let variant_name = &v.name;
+        let cfg = &v.cfg;
+        quote! {
+            #(#cfg)*
+            #name::#variant_name => #ordinal,
+        }
+    });
+    let from_ordinal_checks = repr_enum.variants.iter().zip(&ordinals).map(|(v, ordinal)| {
         let variant_name = &v.name;
+        let cfg = &v.cfg;
         quote! {
-            if value == #name::#variant_name as #repr_type {
+            #(#cfg)*
+            if ordinal == #ordinal {
                 return ::core::option::Option::Some(#name::#variant_name);
+            };
+        }
+    });
+
+    let other_name_arm = other_arm(repr_enum, |other| {
+        let other_name = other.to_string();
+        quote! { #name::#other(_) => #other_name, }
+    });
+    let other_deprecated_arm = other_arm(repr_enum, |other| quote! { #name::#other(_) => false, });
+    let other_ordinal_arm = other_arm(repr_enum, |other| quote! { #name::#other(_) => #ordinal_count, });
+
+    let deprecated_arms = repr_enum.variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let deprecated = v.is_deprecated();
+        let cfg = &v.cfg;
+        quote! {
+            #(#cfg)*
+            #name::#variant_name => #deprecated,
+        }
+    });
+
+    quote! {
+        #allow_deprecated
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The number of variants in `VARIANTS`, excluding hidden variants.
+            pub const COUNT: usize = #count;
+
+            /// The variants in declaration order, excluding hidden variants.
+            pub const VARIANTS: &'static [Self] = #variants;
+
+            /// Returns an iterator over `VARIANTS`.
+            #[inline]
+            pub fn iter() -> ::core::slice::Iter<'static, Self> {
+                Self::VARIANTS.iter()
+            }
+
+            /// Returns the name of the variant.
+            #[inline]
+            pub const fn name(&self) -> &'static str {
+                match self {
+                    #(#name_arms)*
+                    #other_name_arm
+                }
+            }
+
+            /// Returns `true` if the variant is marked `#[deprecated]`.
+            #[inline]
+            pub const fn is_deprecated(&self) -> bool {
+                match self {
+                    #(#deprecated_arms)*
+                    #other_deprecated_arm
+                }
+            }
+
+            /// Returns the position of the variant in declaration order. Hidden
+            /// variants are included, so the position is `COUNT` or more for
+            /// some variants of enums with hidden variants, and an `other`
+            /// variant comes after all of them.
+            #[inline]
+            pub const fn ordinal(&self) -> usize {
+                match self {
+                    #(#ordinal_arms)*
+                    #other_ordinal_arm
+                }
+            }
+
+            /// Returns the variant at the position `ordinal` in declaration order.
+            /// Returns `None` if no variant is at that position.
+            #[inline]
+            pub const fn from_ordinal(ordinal: usize) -> ::core::option::Option<Self> {
+                #(#from_ordinal_checks)*
+                ::core::option::Option::None
+            }
+        }
+    }
+}
+
+/// The number of `variants` as a constant expression. Variants behind `cfg`
+/// are counted only when they are enabled.
+fn count_variants<'a>(variants: impl Iterator<Item = &'a EnumVariant>) -> proc_macro2::TokenStream {
+    let variants: Vec<_> = variants.collect();
+    if variants.iter().all(|v| v.cfg.is_empty()) {
+        let count = variants.len();
+        return quote! { #count };
+    }
+
+    let increments = variants.iter().map(|v| {
+        let cfg = &v.cfg;
+        quote! {
+            #(#cfg)*
+            {
+                count += 1;
+            };
+        }
+    });
+    quote! {{
+        let mut count = 0;
+        #(#increments)*
+        count
+    }}
+}
+
+/// A reference to an array of `variants` for enums with variants behind `cfg`,
+/// which can't be left out of an array expression. The array is filled with an
+/// enabled variant and then overwritten with the enabled variants in order.
+fn gated_variant_array(repr_enum: &ReprEnum, variants: &[&EnumVariant]) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let len = count_variants(variants.iter().copied());
+
+    // The first ungated variant fills the array. If there is none, each gated
+    // variant does so when enabled, and the array is empty when none is.
+    let fill = match variants.iter().find(|v| v.cfg.is_empty()) {
+        Some(v) => {
+            let variant_name = &v.name;
+            quote! { let array = [const { #name::#variant_name }; LEN]; }
+        }
+        None => {
+            let fills = variants.iter().map(|v| {
+                let variant_name = &v.name;
+                let cfg = &v.cfg;
+                quote! {
+                    #(#cfg)*
+                    #[allow(unused_variables)]
+                    let array = [const { #name::#variant_name }; LEN];
+                }
+            });
+            quote! {
+                #[allow(unused_variables)]
+                let array: [#name; 0] = [];
+                #(#fills)*
+            }
+        }
+    };
+
+    let writes = variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let cfg = &v.cfg;
+        quote! {
+            #(#cfg)*
+            {
+                array[index] = #name::#variant_name;
+                index += 1;
+            };
+        }
+    });
+
+    quote! {
+        &{
+            const LEN: usize = #len;
+            #fill
+            let mut array = array;
+            let mut index = 0;
+            #(#writes)*
+            let _ = index;
+            array
+        }
+    }
+}
+
+/// Generate compile-time assertions that the aliases and ranges of each variant
+/// overlap neither the discriminants nor the aliases and ranges of other
+/// variants. Pairs whose values were all known during parsing have already
+/// been checked there.
+fn generate_overlap_checks(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let allow_deprecated = allow_deprecated(repr_enum);
+
+    // Bounds are `None` at the limits of the repr type
+    struct Entry<'a> {
+        variant: &'a syn::Ident,
+        cfg: &'a [syn::Attribute],
+        start: Option<proc_macro2::TokenStream>,
+        end: Option<proc_macro2::TokenStream>,
+        known: bool,
+        accepted: bool,
+        description: String,
+        span: proc_macro2::Span,
+    }
+
+    let mut entries = Vec::new();
+    for v in &repr_enum.variants {
+        let variant_name = &v.name;
+        let discriminant = variant_value(repr_enum, v);
+        entries.push(Entry {
+            variant: variant_name,
+            cfg: &v.cfg,
+            start: Some(discriminant.clone()),
+            end: Some(discriminant),
+            known: v.value.is_some(),
+            accepted: false,
+            description: format!("the discriminant of `{}`", variant_name),
+            span: variant_name.span(),
+        });
+    }
+    for v in &repr_enum.variants {
+        for accepted in &v.options.accepts {
+            entries.push(Entry {
+                variant: &v.name,
+                cfg: &v.cfg,
+                start: accepted.start.as_ref().map(|start| quote! { #start }),
+                end: accepted.end.as_ref().map(|end| quote! { #end }),
+                known: accepted.bounds.is_some(),
+                accepted: true,
+                description: accepted.describe(&v.name),
+                span: syn::spanned::Spanned::span(&accepted.source),
+            });
+        }
+    }
+
+    let mut checks = Vec::new();
+    for (i, later) in entries.iter().enumerate() {
+        for earlier in &entries[..i] {
+            if !later.accepted || later.variant == earlier.variant || (later.known && earlier.known) {
+                continue;
+            }
+            // Two ranges overlap if each starts before the other ends
+            let conditions: Vec<_> = [(&later.start, &earlier.end), (&earlier.start, &later.end)]
+                .into_iter()
+                .filter_map(|(start, end)| match (start, end) {
+                    (Some(start), Some(end)) => Some(quote! { (#start) <= (#end) }),
+                    _ => None,
+                })
+                .collect();
+            let overlaps = if conditions.is_empty() {
+                quote! { true }
+            } else {
+                quote! { #(#conditions)&&* }
+            };
+            let message = format!("{} overlaps {}", later.description, earlier.description);
+            let cfg = later.cfg.iter().chain(earlier.cfg);
+            checks.push(quote_spanned! {later.span=>
+                #(#cfg)*
+                ::core::assert!(!(#overlaps), #message);
+            });
+        }
+    }
+
+    if checks.is_empty() {
+        return quote! {};
+    }
+    quote! {
+        #allow_deprecated
+        const _: () = {
+            #(#checks)*
+        };
+    }
+}
+
+/// The expression used by the `TryFrom` conversions to decode `value`, a repr
+/// value: `from_repr`, or `from_repr_masked` if the `mask` applies to them.
+/// With `strict`, `from_repr_strict` is used instead, on the masked value.
+fn try_from_decode(
+    repr_enum: &ReprEnum,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let masked = repr_enum.options.mask.is_some() && repr_enum.options.mask_try_from;
+    match (repr_enum.options.strict, masked) {
+        (true, true) => quote! { Self::from_repr_strict(#value & Self::DECODE_MASK) },
+        (true, false) => quote! { Self::from_repr_strict(#value) },
+        (false, true) => quote! { Self::from_repr_masked(#value) },
+        (false, false) => quote! { Self::from_repr(#value) },
+    }
+}
+
+/// The value of the variant `v` as a constant expression of the repr type: the
+/// variant cast to the repr type, or its discriminant expression for enums with
+/// an `other` variant, which rule out casts.
+fn variant_value(repr_enum: &ReprEnum, v: &EnumVariant) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let variant_name = &v.name;
+    let repr_type = &repr_enum.repr_type;
+    if repr_enum.options.other.is_some() {
+        let value = v.calculated_discriminant.as_value_tokens();
+        quote! { ::core::convert::identity::<#repr_type>(#value) }
+    } else {
+        quote! { #name::#variant_name as #repr_type }
+    }
+}
+
+/// A discriminant for the `other` variant that no variant uses: the one after
+/// the largest discriminant if it fits the repr type, or else the smallest
+/// unused value. `None` if a discriminant is not known at expansion time, and
+/// the `other` variant then follows the last variant.
+fn other_discriminant(repr_enum: &ReprEnum) -> Option<i128> {
+    let int_type = IntType::from_ident(&repr_enum.repr_type).expect("repr type is validated during parsing");
+    let values = repr_enum.variants.iter().map(|v| v.value).collect::<Option<Vec<_>>>()?;
+    let after_largest = values.iter().max().and_then(|max| max.checked_add(1));
+    after_largest
+        .filter(|&value| int_type.contains(value))
+        .or_else(|| (int_type.min_value()..=int_type.max_value()).find(|value| !values.contains(value)))
+}
+
+/// Compile-time assertions that `check` holds for the value of every variant,
+/// failing with the `message` for the variant. Discriminants known at expansion
+/// time are validated during parsing; these catch the ones that could not be
+/// evaluated. Each assertion points at its variant and carries its `cfg`.
+fn discriminant_checks(
+    repr_enum: &ReprEnum,
+    check: impl Fn(proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+    message: impl Fn(&syn::Ident) -> String,
+) -> Vec<proc_macro2::TokenStream> {
+    repr_enum
+        .variants
+        .iter()
+        .map(|v| {
+            let variant_name = &v.name;
+            let condition = check(variant_value(repr_enum, v));
+            let message = message(variant_name);
+            let cfg = &v.cfg;
+            quote_spanned! {variant_name.span()=>
+                #(#cfg)*
+                ::core::assert!(#condition, #message);
+            }
+        })
+        .collect()
+}
+
+/// A match arm for the `other` variant built by `arm` from its name, if the
+/// enum has one.
+fn other_arm(
+    repr_enum: &ReprEnum,
+    arm: impl FnOnce(&syn::Ident) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    repr_enum.options.other.as_ref().map(arm).unwrap_or_default()
+}
+
+/// `#[allow(deprecated)]` for generated code naming the variants, if any of
+/// them is deprecated.
+fn allow_deprecated(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    if repr_enum.variants.iter().any(EnumVariant::is_deprecated) {
+        quote! { #[allow(deprecated)] }
+    } else {
+        quote! {}
+    }
+}
+
+/// The expression building the conversion error for `value`, an expression of
+/// the repr type that didn't match any variant. With `reserved`, the error kind
+/// depends on whether the value is reserved.
+fn conversion_error(
+    repr_enum: &ReprEnum,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let error_type_name = format_ident!("{}ConversionError", repr_enum.name);
+    if repr_enum.options.reserved.is_empty() {
+        quote! { #error_type_name(#value) }
+    } else {
+        quote! {
+            if Self::is_reserved(#value) {
+                #error_type_name::Reserved(#value)
+            } else {
+                #error_type_name::Unknown(#value)
+            }
+        }
+    }
+}
+
+/// Generate the From<Enum> for T trait implementations.
+/// Generates both owned and reference implementations:
+/// - `From<Enum> for T` - converts owned enum to integer
+/// - `From<&Enum> for T` - converts enum reference to integer
+///
+/// With the `newtype` option, also generates `From<Enum>` and `From<&Enum>`
+/// for the newtype.
+fn generate_from_impl(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let repr_type = &repr_enum.repr_type;
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+
+    let newtype_impls = repr_enum.options.newtype.as_ref().map(|newtype| {
+        let newtype_ty = &newtype.ty;
+        let construct = match &newtype.new {
+            Some(new) => quote! { #newtype_ty::#new(value.as_repr()) },
+            None => quote! { #newtype_ty(value.as_repr()) },
+        };
+
+        quote! {
+            // Convert owned enum to newtype
+            impl #impl_generics ::core::convert::From<#name #ty_generics> for #newtype_ty #where_clause {
+                #[inline]
+                fn from(value: #name #ty_generics) -> Self {
+                    #construct
+                }
+            }
+
+            // Convert enum reference to newtype
+            impl #impl_generics ::core::convert::From<&#name #ty_generics> for #newtype_ty #where_clause {
+                #[inline]
+                fn from(value: &#name #ty_generics) -> Self {
+                    #construct
+                }
+            }
+        }
+    });
+
+    quote! {
+        #newtype_impls
+
+        // Convert owned enum to integer
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for #repr_type #where_clause {
+            #[inline]
+            fn from(value: #name #ty_generics) -> Self {
+                value.as_repr()
+            }
+        }
+
+        // Convert enum reference to integer
+        impl #impl_generics ::core::convert::From<&#name #ty_generics> for #repr_type #where_clause {
+            #[inline]
+            fn from(value: &#name #ty_generics) -> Self {
+                value.as_repr()
+            }
+        }
+    }
+}
+
+/// Generate the TryFrom<T> for Enum trait implementations.
+/// Generates both owned and reference implementations:
+/// - `TryFrom<T> for Enum` - converts owned integer to enum
+/// - `TryFrom<&T> for Enum` - converts integer reference to enum
+///
+/// With the `newtype` option, also generates `TryFrom<Newtype> for Enum`.
+fn generate_try_from_impl(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let repr_type = &repr_enum.repr_type;
+    let error_type_name = format_ident!("{}ConversionError", name);
+    let decode_value = try_from_decode(repr_enum, quote! { value });
+    let decode_ref = try_from_decode(repr_enum, quote! { *value });
+    let unknown = conversion_error(repr_enum, quote! { value });
+    let unknown_ref = conversion_error(repr_enum, quote! { *value });
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+
+    // With an `other` variant every value converts, and `From` takes the place of `TryFrom`
+    if let Some(other) = &repr_enum.options.other {
+        return generate_other_from_impl(repr_enum, other);
+    }
+
+    let newtype_impl = repr_enum.options.newtype.as_ref().map(|newtype| {
+        let newtype_ty = &newtype.ty;
+        let access = match &newtype.get {
+            Some(get) => quote! { value.#get() },
+            None => quote! { value.0 },
+        };
+
+        quote! {
+            // Convert newtype to enum
+            impl #impl_generics ::core::convert::TryFrom<#newtype_ty> for #name #ty_generics #where_clause {
+                type Error = #error_type_name;
+
+                #[inline]
+                fn try_from(value: #newtype_ty) -> ::core::result::Result<Self, Self::Error> {
+                    let value: #repr_type = #access;
+                    #decode_value.ok_or(#unknown)
+                }
+            }
+        }
+    });
+
+    quote! {
+        #newtype_impl
+
+        // Convert owned integer to enum
+        impl #impl_generics ::core::convert::TryFrom<#repr_type> for #name #ty_generics #where_clause {
+            type Error = #error_type_name;
+
+            #[inline]
+            fn try_from(value: #repr_type) -> ::core::result::Result<Self, Self::Error> {
+                #decode_value.ok_or(#unknown)
+            }
+        }
+
+        // Convert integer reference to enum
+        impl #impl_generics ::core::convert::TryFrom<&#repr_type> for #name #ty_generics #where_clause {
+            type Error = #error_type_name;
+
+            #[inline]
+            fn try_from(value: &#repr_type) -> ::core::result::Result<Self, Self::Error> {
+                #decode_ref.ok_or(#unknown_ref)
+            }
+        }
+    }
+}
+
+/// Generate the infallible conversions of an enum with an `other` variant,
+/// which holds the values that match no other variant:
+/// - `From<T> for Enum` and `From<&T> for Enum`
+/// - `From<Newtype> for Enum` with `newtype`
+fn generate_other_from_impl(repr_enum: &ReprEnum, other: &syn::Ident) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let repr_type = &repr_enum.repr_type;
+    let decode_value = try_from_decode(repr_enum, quote! { value });
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+
+    let newtype_impl = repr_enum.options.newtype.as_ref().map(|newtype| {
+        let newtype_ty = &newtype.ty;
+        let access = match &newtype.get {
+            Some(get) => quote! { value.#get() },
+            None => quote! { value.0 },
+        };
+
+        quote! {
+            // Convert newtype to enum
+            impl #impl_generics ::core::convert::From<#newtype_ty> for #name #ty_generics #where_clause {
+                #[inline]
+                fn from(value: #newtype_ty) -> Self {
+                    let value: #repr_type = #access;
+                    <Self as ::core::convert::From<#repr_type>>::from(value)
+                }
+            }
+        }
+    });
+
+    quote! {
+        #newtype_impl
+
+        // Convert owned integer to enum
+        impl #impl_generics ::core::convert::From<#repr_type> for #name #ty_generics #where_clause {
+            #[inline]
+            fn from(value: #repr_type) -> Self {
+                match #decode_value {
+                    ::core::option::Option::Some(variant) => variant,
+                    ::core::option::Option::None => Self::#other(value),
+                }
+            }
+        }
+
+        // Convert integer reference to enum
+        impl #impl_generics ::core::convert::From<&#repr_type> for #name #ty_generics #where_clause {
+            #[inline]
+            fn from(value: &#repr_type) -> Self {
+                <Self as ::core::convert::From<#repr_type>>::from(*value)
+            }
+        }
+    }
+}
+
+/// Generate the error type for failed conversions.
+///
+/// The error carries the repr value that failed to convert. With `reserved`,
+/// the error is an enum telling reserved values apart from unknown ones.
+fn generate_error_type(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let error_type_name = format_ident!("{}ConversionError", repr_enum.name);
+    conversion_error_type(repr_enum, &error_type_name, false)
+}
+
+/// Generate a conversion error type named `error_type_name`.
+///
+/// The error carries the repr value, or with `generic` a value of its type
+/// parameter `T`, as used by the conversions from the `also` types.
+fn conversion_error_type(repr_enum: &ReprEnum, error_type_name: &syn::Ident, generic: bool) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let repr_type = &repr_enum.repr_type;
+    let vis = &repr_enum.visibility;
+    let (params, display_params, error_params, value_type) = if generic {
+        (
+            quote! { <T> },
+            quote! { <T: ::core::fmt::Display> },
+            quote! { <T: ::core::fmt::Debug + ::core::fmt::Display> },
+            quote! { T },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {}, quote! { #repr_type })
+    };
+
+    if !repr_enum.options.reserved.is_empty() {
+        return quote! {
+            /// Error type returned when trying to convert an integer to this enum
+            /// but the value doesn't match any variant.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #vis enum #error_type_name #params {
+                /// The value is reserved for future use.
+                Reserved(#value_type),
+                /// The value is neither a variant nor reserved.
+                Unknown(#value_type),
+            }
+
+            impl #params #error_type_name #params {
+                /// Returns the value that failed to convert.
+                #[inline]
+                pub fn value(self) -> #value_type {
+                    match self {
+                        Self::Reserved(value) | Self::Unknown(value) => value,
+                    }
+                }
+            }
+
+            impl #display_params ::core::fmt::Display for #error_type_name #params {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        Self::Reserved(value) => write!(f, "reserved {} value: {}", stringify!(#name), value),
+                        Self::Unknown(value) => write!(f, "unknown {} variant: {}", stringify!(#name), value),
+                    }
+                }
+            }
+
+            impl #error_params ::core::error::Error for #error_type_name #params {}
+        };
+    }
+
+    quote! {
+        /// Error type returned when trying to convert an integer to this enum
+        /// but the value doesn't match any known variant.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis struct #error_type_name #params (pub #value_type);
+
+        impl #display_params ::core::fmt::Display for #error_type_name #params {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                write!(f, "unknown {} variant: {}", stringify!(#name), self.0)
+            }
+        }
+
+        impl #error_params ::core::error::Error for #error_type_name #params {}
+    }
+}
+
+/// Generate conversions to and from the additional integer types given with `also`.
+///
+/// For each type `U`:
+/// - `From<Enum> for U` and `From<&Enum> for U` if every repr value converts
+///   losslessly to `U`, otherwise `TryFrom<Enum> for U` and `TryFrom<&Enum> for U`
+/// - `TryFrom<U> for Enum` and `TryFrom<&U> for Enum`, whose error
+///   `{Name}IntConversionError<U>` carries the original `U` value
+fn generate_also_impls(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    if repr_enum.options.also.is_empty() {
+        return quote! {};
+    }
+
+    let name = &repr_enum.name;
+    let repr_type = &repr_enum.repr_type;
+    let error_type_name = format_ident!("{}IntConversionError", name);
+    let error_type = conversion_error_type(repr_enum, &error_type_name, true);
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+    let int_type = IntType::from_ident(repr_type).expect("repr type is validated during parsing");
+    let decode_value = try_from_decode(repr_enum, quote! { value });
+    // Values out of range for the repr type are never reserved
+    let unknown = if repr_enum.options.reserved.is_empty() {
+        quote! { #error_type_name(value) }
+    } else {
+        quote! {
+            if #repr_type::try_from(value).is_ok_and(Self::is_reserved) {
+                #error_type_name::Reserved(value)
+            } else {
+                #error_type_name::Unknown(value)
+            }
+        }
+    };
+
+    // With an `other` variant only values out of range for the repr type fail
+    let convert_value = if repr_enum.options.other.is_some() {
+        let out_of_range = if repr_enum.options.reserved.is_empty() {
+            quote! { #error_type_name(value) }
+        } else {
+            quote! { #error_type_name::Unknown(value) }
+        };
+        quote! {
+            #repr_type::try_from(value)
+                .map(<Self as ::core::convert::From<#repr_type>>::from)
+                .map_err(|_| #out_of_range)
+        }
+    } else {
+        quote! {
+            #repr_type::try_from(value)
+                .ok()
+                .and_then(|value| #decode_value)
+                .ok_or(#unknown)
+        }
+    };
+
+    let impls = repr_enum.options.also.iter().map(|target| {
+        let target_type = IntType::from_ident(target).expect("also types are validated during parsing");
+
+        let into_target = if int_type.converts_losslessly_to(target_type) {
+            quote! {
+                // Convert owned enum to the wider integer
+                impl #impl_generics ::core::convert::From<#name #ty_generics> for #target #where_clause {
+                    #[inline]
+                    fn from(value: #name #ty_generics) -> Self {
+                        #target::from(value.as_repr())
+                    }
+                }
+
+                // Convert enum reference to the wider integer
+                impl #impl_generics ::core::convert::From<&#name #ty_generics> for #target #where_clause {
+                    #[inline]
+                    fn from(value: &#name #ty_generics) -> Self {
+                        #target::from(value.as_repr())
+                    }
+                }
+            }
+        } else {
+            quote! {
+                // Convert owned enum to an integer that may not hold every repr value
+                impl #impl_generics ::core::convert::TryFrom<#name #ty_generics> for #target #where_clause {
+                    type Error = ::core::num::TryFromIntError;
+
+                    #[inline]
+                    fn try_from(value: #name #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                        #target::try_from(value.as_repr())
+                    }
+                }
+
+                // Convert enum reference to an integer that may not hold every repr value
+                impl #impl_generics ::core::convert::TryFrom<&#name #ty_generics> for #target #where_clause {
+                    type Error = ::core::num::TryFromIntError;
+
+                    #[inline]
+                    fn try_from(value: &#name #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                        #target::try_from(value.as_repr())
+                    }
+                }
+            }
+        };
+
+        quote! {
+            #into_target
+
+            // Convert owned integer to enum, keeping the original value on error
+            impl #impl_generics ::core::convert::TryFrom<#target> for #name #ty_generics #where_clause {
+                type Error = #error_type_name<#target>;
+
+                #[inline]
+                fn try_from(value: #target) -> ::core::result::Result<Self, Self::Error> {
+                    #convert_value
+                }
+            }
+
+            // Convert integer reference to enum, keeping the original value on error
+            impl #impl_generics ::core::convert::TryFrom<&#target> for #name #ty_generics #where_clause {
+                type Error = #error_type_name<#target>;
+
+                #[inline]
+                fn try_from(value: &#target) -> ::core::result::Result<Self, Self::Error> {
+                    <Self as ::core::convert::TryFrom<#target>>::try_from(*value)
+                }
+            }
+        }
+    });
+
+    quote! {
+        #error_type
+
+        #(#impls)*
+    }
+}
+
+/// Generate `NonZero` conversions for an enum without a zero discriminant:
+/// - `as_nonzero()` method
+/// - `From<Enum> for NonZeroT` and `From<&Enum> for NonZeroT`
+/// - `TryFrom<NonZeroT> for Enum` and `TryFrom<&NonZeroT> for Enum`
+/// - Compile-time assertions that no discriminant is zero and that
+///   `Option<Enum>` has the same size as the repr type
+fn generate_nonzero_impls(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let allow_deprecated = allow_deprecated(repr_enum);
+    let repr_type = &repr_enum.repr_type;
+    let error_type_name = format_ident!("{}ConversionError", name);
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+    let int_type = IntType::from_ident(repr_type).expect("repr type is validated during parsing");
+    let nonzero_type = format_ident!("{}", int_type.nonzero_name());
+    let decode_nonzero = try_from_decode(repr_enum, quote! { value.get() });
+    let unknown = conversion_error(repr_enum, quote! { value.get() });
+
+    let nonzero_checks = discriminant_checks(
+        repr_enum,
+        |discriminant| quote! { #discriminant != 0 },
+        |variant_name| format!("discriminant of `{}` is zero", variant_name),
+    );
+
+    quote! {
+        #allow_deprecated
+        const _: () = {
+            #(#nonzero_checks)*
+            ::core::assert!(
+                ::core::mem::size_of::<::core::option::Option<#name>>()
+                    == ::core::mem::size_of::<#repr_type>(),
+                concat!("`Option<", stringify!(#name), ">` is larger than its repr type"),
+            );
+        };
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Converts the enum variant to its non-zero integer representation.
+            #[inline]
+            pub const fn as_nonzero(&self) -> ::core::num::#nonzero_type {
+                match ::core::num::#nonzero_type::new(self.as_repr()) {
+                    ::core::option::Option::Some(value) => value,
+                    ::core::option::Option::None => ::core::unreachable!(),
+                }
+            }
+        }
+
+        // Convert owned enum to non-zero integer
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for ::core::num::#nonzero_type #where_clause {
+            #[inline]
+            fn from(value: #name #ty_generics) -> Self {
+                value.as_nonzero()
+            }
+        }
+
+        // Convert enum reference to non-zero integer
+        impl #impl_generics ::core::convert::From<&#name #ty_generics> for ::core::num::#nonzero_type #where_clause {
+            #[inline]
+            fn from(value: &#name #ty_generics) -> Self {
+                value.as_nonzero()
+            }
+        }
+
+        // Convert owned non-zero integer to enum
+        impl #impl_generics ::core::convert::TryFrom<::core::num::#nonzero_type> for #name #ty_generics #where_clause {
+            type Error = #error_type_name;
+
+            #[inline]
+            fn try_from(value: ::core::num::#nonzero_type) -> ::core::result::Result<Self, Self::Error> {
+                #decode_nonzero.ok_or(#unknown)
+            }
+        }
+
+        // Convert non-zero integer reference to enum
+        impl #impl_generics ::core::convert::TryFrom<&::core::num::#nonzero_type> for #name #ty_generics #where_clause {
+            type Error = #error_type_name;
+
+            #[inline]
+            fn try_from(value: &::core::num::#nonzero_type) -> ::core::result::Result<Self, Self::Error> {
+                #decode_nonzero.ok_or(#unknown)
+            }
+        }
+    }
+}
+
+/// Generate sentinel encoding for `Option<Enum>`, where `sentinel` encodes `None`:
+/// - `NONE_SENTINEL` constant
+/// - `encode_option()` and `decode_option()` methods
+/// - A compile-time assertion that the sentinel is not a discriminant
+fn generate_sentinel_impls(repr_enum: &ReprEnum, sentinel: &syn::Expr) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let allow_deprecated = allow_deprecated(repr_enum);
+    let repr_type = &repr_enum.repr_type;
+    let error_type_name = format_ident!("{}ConversionError", name);
+    let unknown = conversion_error(repr_enum, quote! { value });
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+
+    let collision_checks = discriminant_checks(
+        repr_enum,
+        |discriminant| quote! { #discriminant != #name::NONE_SENTINEL },
+        |variant_name| format!("discriminant of `{}` collides with the `none` sentinel", variant_name),
+    );
+
+    // Values that match no variant are kept by the `other` variant
+    let decode_unknown = match &repr_enum.options.other {
+        Some(other) => quote! {
+            ::core::result::Result::Ok(::core::option::Option::Some(Self::#other(value)))
+        },
+        None => quote! { ::core::result::Result::Err(#unknown) },
+    };
+
+    quote! {
+        #allow_deprecated
+        const _: () = {
+            #(#collision_checks)*
+        };
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The integer value that encodes `None` in `encode_option` and `decode_option`.
+            pub const NONE_SENTINEL: #repr_type = #sentinel;
+
+            /// Encodes an optional enum value as an integer, using
+            /// `NONE_SENTINEL` for `None`.
+            #[inline]
+            pub const fn encode_option(value: ::core::option::Option<Self>) -> #repr_type {
+                match value {
+                    ::core::option::Option::Some(value) => value.as_repr(),
+                    ::core::option::Option::None => Self::NONE_SENTINEL,
+                }
+            }
+
+            /// Decodes an integer into an optional enum value, mapping
+            /// `NONE_SENTINEL` to `None`.
+            /// Returns an error if the value is neither the sentinel nor a variant.
+            #[inline]
+            pub const fn decode_option(
+                value: #repr_type,
+            ) -> ::core::result::Result<::core::option::Option<Self>, #error_type_name> {
+                if value == Self::NONE_SENTINEL {
+                    return ::core::result::Result::Ok(::core::option::Option::None);
+                }
+                match Self::from_repr(value) {
+                    ::core::option::Option::Some(value) => {
+                        ::core::result::Result::Ok(::core::option::Option::Some(value))
+                    }
+                    ::core::option::Option::None => #decode_unknown,
+                }
+            }
+        }
+    }
+}
+
+/// Generate packing helpers for an enum limited to `bits` bits:
+/// - `BITS` and `MASK` constants
+/// - `pack_into()` and `unpack_from()` methods for `u32` words
+/// - A compile-time assertion that every discriminant fits in `BITS`
+fn generate_bits_impls(repr_enum: &ReprEnum, bits: &syn::LitInt) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let allow_deprecated = allow_deprecated(repr_enum);
+    let repr_type = &repr_enum.repr_type;
+    let error_type_name = format_ident!("{}ConversionError", name);
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+    let unknown = conversion_error(repr_enum, quote! { value });
+    let width: u32 = bits.base10_parse().expect("bits is validated during parsing");
+    let mask = proc_macro2::Literal::u64_unsuffixed((1u64 << width) - 1);
+
+    let width_checks = discriminant_checks(
+        repr_enum,
+        |discriminant| quote! { #discriminant & !#name::MASK == 0 },
+        |variant_name| format!("discriminant of `{}` does not fit in {} bits", variant_name, width),
+    );
+
+    quote! {
+        #allow_deprecated
+        const _: () = {
+            #(#width_checks)*
+        };
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Number of bits used by the enum when packed into a larger word.
+            pub const BITS: u32 = #bits;
+
+            /// Mask covering the low `BITS` bits of the repr type.
+            pub const MASK: #repr_type = #mask;
+
+            /// Stores the enum in `BITS` bits of `word` starting at bit `shift`,
+            /// leaving the other bits unchanged.
+            ///
+            /// # Panics
+            /// Panics if the field does not fit in a `u32` at `shift`.
+            #[inline]
+            pub const fn pack_into(&self, word: u32, shift: u32) -> u32 {
+                ::core::assert!(shift <= u32::BITS - Self::BITS, "field does not fit in the word");
+                let mask = (Self::MASK as u32) << shift;
+                (word & !mask) | ((self.as_repr() as u32) << shift)
+            }
+
+            /// Reads the enum from `BITS` bits of `word` starting at bit `shift`.
+            /// Returns an error if the bits don't match any variant.
+            ///
+            /// # Panics
+            /// Panics if the field does not fit in a `u32` at `shift`.
+            #[inline]
+            pub const fn unpack_from(
+                word: u32,
+                shift: u32,
+            ) -> ::core::result::Result<Self, #error_type_name> {
+                ::core::assert!(shift <= u32::BITS - Self::BITS, "field does not fit in the word");
+                let value = ((word >> shift) & (Self::MASK as u32)) as #repr_type;
+                match Self::from_repr(value) {
+                    ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                    ::core::option::Option::None => ::core::result::Result::Err(#unknown),
+                }
+            }
+        }
+    }
+}
+
+/// Generate masked decoding for an enum whose values only occupy the bits in `mask`:
+/// - `DECODE_MASK` constant
+/// - `from_repr_masked()` method, ignoring bits outside the mask
+/// - A compile-time assertion that every discriminant lies within the mask
+fn generate_mask_impls(repr_enum: &ReprEnum, mask: &syn::Expr) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let allow_deprecated = allow_deprecated(repr_enum);
+    let repr_type = &repr_enum.repr_type;
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+
+    let mask_checks = discriminant_checks(
+        repr_enum,
+        |discriminant| quote! { #discriminant & !#name::DECODE_MASK == 0 },
+        |variant_name| format!("discriminant of `{}` has bits outside of the `mask`", variant_name),
+    );
+
+    quote! {
+        #allow_deprecated
+        const _: () = {
+            #(#mask_checks)*
+        };
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Mask of the bits that carry the enum value; other bits are reserved.
+            pub const DECODE_MASK: #repr_type = #mask;
+
+            /// Converts an integer value to the enum variant, ignoring the bits
+            /// outside of `DECODE_MASK`.
+            /// Returns `None` if the masked value doesn't match any variant.
+            #[inline]
+            pub const fn from_repr_masked(value: #repr_type) -> ::core::option::Option<Self> {
+                Self::from_repr(value & Self::DECODE_MASK)
+            }
+        }
+    }
+}
+
+/// Generate helpers for values reserved for future use:
+/// - `is_reserved()` method
+/// - A compile-time assertion that no discriminant is reserved
+fn generate_reserved_impls(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let allow_deprecated = allow_deprecated(repr_enum);
+    let repr_type = &repr_enum.repr_type;
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+
+    // Comparisons rather than range patterns, so bounds can be any const expression
+    let conditions = repr_enum.options.reserved.iter().map(|entry| match entry {
+        syn::Expr::Range(range) => {
+            let start = range.start.as_ref().map(|start| quote! { value >= (#start) });
+            let end = range.end.as_ref().map(|end| match range.limits {
+                syn::RangeLimits::Closed(_) => quote! { value <= (#end) },
+                syn::RangeLimits::HalfOpen(_) => quote! { value < (#end) },
+            });
+            let bounds: Vec<_> = start.into_iter().chain(end).collect();
+            if bounds.is_empty() {
+                quote! { true }
+            } else {
+                quote! { (#(#bounds)&&*) }
+            }
+        }
+        value => quote! { value == (#value) },
+    });
+
+    let reserved_checks = discriminant_checks(
+        repr_enum,
+        |discriminant| quote! { !#name::is_reserved(#discriminant) },
+        |variant_name| format!("discriminant of `{}` falls in a reserved range", variant_name),
+    );
+
+    quote! {
+        #allow_deprecated
+        const _: () = {
+            #(#reserved_checks)*
+        };
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Returns `true` if the value is reserved for future use.
+            #[inline]
+            pub const fn is_reserved(value: #repr_type) -> bool {
+                #(#conditions)||*
+            }
+        }
+    }
+}
+
+/// Generate the `{Name}Flags` set type for a `flags` enum:
+/// - Construction from and conversion to the repr type (`from_bits()`, `bits()`)
+/// - `contains()`, `insert()`, `remove()` and iteration over the set variants
+/// - `BitOr`, `BitAnd` and `Not`, including `Variant | Variant`
+/// - A compile-time assertion that every discriminant is a single bit
+fn generate_flags_impls(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let allow_deprecated = allow_deprecated(repr_enum);
+    let repr_type = &repr_enum.repr_type;
+    let vis = &repr_enum.visibility;
+    let flags_type_name = format_ident!("{}Flags", name);
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+
+    let flags_checks = discriminant_checks(
+        repr_enum,
+        |discriminant| quote! { (#discriminant).count_ones() == 1 },
+        |variant_name| {
+            format!(
+                "discriminant of `{}` is not a single bit, which the `flags` option requires",
+                variant_name
+            )
+        },
+    );
+
+    let all_bits = repr_enum.variants.iter().map(|v| {
+        let discriminant = variant_value(repr_enum, v);
+        let cfg = &v.cfg;
+        quote! {
+            #(#cfg)*
+            {
+                bits |= #discriminant;
+            };
+        }
+    });
+
+    // Yields owned variants without requiring the enum to be `Copy`
+    let owned_arms = repr_enum.variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let cfg = &v.cfg;
+        quote! {
+            #(#cfg)*
+            #name::#variant_name => #name::#variant_name,
+        }
+    });
+
+    quote! {
+        #allow_deprecated
+        const _: () = {
+            #(#flags_checks)*
+        };
+
+        /// A set of flags, each a variant of the enum.
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+        #vis struct #flags_type_name(#repr_type);
+
+        #allow_deprecated
+        impl #flags_type_name {
+            const ALL_BITS: #repr_type = {
+                let mut bits = 0;
+                #(#all_bits)*
+                bits
+            };
+
+            /// Returns the empty set.
+            #[inline]
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+
+            /// Returns the set of all variants.
+            #[inline]
+            pub const fn all() -> Self {
+                Self(Self::ALL_BITS)
+            }
+
+            /// Returns the raw bits of the set.
+            #[inline]
+            pub const fn bits(&self) -> #repr_type {
+                self.0
+            }
+
+            /// Converts raw bits to a set.
+            /// Returns `None` if any bit doesn't belong to a variant.
+            #[inline]
+            pub const fn from_bits(bits: #repr_type) -> ::core::option::Option<Self> {
+                if bits & !Self::ALL_BITS == 0 {
+                    ::core::option::Option::Some(Self(bits))
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+
+            /// Converts raw bits to a set, dropping the bits that don't belong
+            /// to a variant.
+            #[inline]
+            pub const fn from_bits_truncate(bits: #repr_type) -> Self {
+                Self(bits & Self::ALL_BITS)
+            }
+
+            /// Returns `true` if no flag is set.
+            #[inline]
+            pub const fn is_empty(&self) -> bool {
+                self.0 == 0
+            }
+
+            /// Returns `true` if every flag is set.
+            #[inline]
+            pub const fn is_all(&self) -> bool {
+                self.0 == Self::ALL_BITS
+            }
+
+            /// Returns `true` if every flag of `other` is set.
+            #[inline]
+            pub fn contains(&self, other: impl ::core::convert::Into<Self>) -> bool {
+                let other = other.into();
+                self.0 & other.0 == other.0
+            }
+
+            /// Sets the flags of `other`.
+            #[inline]
+            pub fn insert(&mut self, other: impl ::core::convert::Into<Self>) {
+                self.0 |= other.into().0;
+            }
+
+            /// Clears the flags of `other`.
+            #[inline]
+            pub fn remove(&mut self, other: impl ::core::convert::Into<Self>) {
+                self.0 &= !other.into().0;
+            }
+
+            /// Returns an iterator over the set variants in declaration order,
+            /// excluding hidden variants.
+            pub fn iter(&self) -> impl ::core::iter::Iterator<Item = #name> + 'static {
+                let bits = self.0;
+                #name::VARIANTS
+                    .iter()
+                    .filter(move |flag| bits & flag.as_repr() != 0)
+                    .map(|flag| match flag {
+                        #(#owned_arms)*
+                    })
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for #flags_type_name #where_clause {
+            #[inline]
+            fn from(flag: #name #ty_generics) -> Self {
+                Self(flag.as_repr())
+            }
+        }
+
+        impl ::core::ops::BitOr for #flags_type_name {
+            type Output = Self;
+
+            #[inline]
+            fn bitor(self, other: Self) -> Self {
+                Self(self.0 | other.0)
+            }
+        }
+
+        impl #impl_generics ::core::ops::BitOr<#name #ty_generics> for #flags_type_name #where_clause {
+            type Output = Self;
+
+            #[inline]
+            fn bitor(self, other: #name #ty_generics) -> Self {
+                Self(self.0 | other.as_repr())
+            }
+        }
+
+        impl #impl_generics ::core::ops::BitOr for #name #ty_generics #where_clause {
+            type Output = #flags_type_name;
+
+            #[inline]
+            fn bitor(self, other: Self) -> #flags_type_name {
+                #flags_type_name(self.as_repr() | other.as_repr())
+            }
+        }
+
+        impl ::core::ops::BitOrAssign for #flags_type_name {
+            #[inline]
+            fn bitor_assign(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+        }
+
+        impl ::core::ops::BitAnd for #flags_type_name {
+            type Output = Self;
+
+            #[inline]
+            fn bitand(self, other: Self) -> Self {
+                Self(self.0 & other.0)
+            }
+        }
+
+        impl ::core::ops::BitAndAssign for #flags_type_name {
+            #[inline]
+            fn bitand_assign(&mut self, other: Self) {
+                self.0 &= other.0;
+            }
+        }
+
+        impl ::core::ops::Not for #flags_type_name {
+            type Output = Self;
+
+            #[inline]
+            fn not(self) -> Self {
+                Self(!self.0 & Self::ALL_BITS)
+            }
+        }
+
+        impl #impl_generics ::core::iter::FromIterator<#name #ty_generics> for #flags_type_name #where_clause {
+            fn from_iter<I: ::core::iter::IntoIterator<Item = #name #ty_generics>>(iter: I) -> Self {
+                iter.into_iter().fold(Self::empty(), |flags, flag| flags | flag)
+            }
+        }
+
+        // Prints the names of the set flags, e.g. `PermissionFlags(Read | Write)`
+        impl ::core::fmt::Debug for #flags_type_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(::core::stringify!(#flags_type_name))?;
+                f.write_str("(")?;
+                let mut rest = self.0;
+                for (i, flag) in self.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" | ")?;
+                    }
+                    f.write_str(flag.name())?;
+                    rest &= !flag.as_repr();
+                }
+                // Hidden variants are not iterated
+                if rest != 0 {
+                    if rest != self.0 {
+                        f.write_str(" | ")?;
+                    }
+                    write!(f, "{:#x}", rest)?;
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+/// Generate the `{Name}Set` bitset type for a `set` enum, backed by the smallest
+/// unsigned integer with a bit for every variant, where each variant's bit is
+/// its ordinal:
+/// - Const construction (`empty()`, `all()`, `with()`, `without()`)
+/// - `contains()`, `insert()`, `remove()`, `len()` and iteration in declaration order
+/// - Set algebra, as methods and as the `BitOr`, `BitAnd`, `BitXor`, `Sub` and `Not` operators
+/// - Conversion to and from raw bits (`bits()`, `from_bits()`)
+fn generate_set_impls(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let vis = &repr_enum.visibility;
+    let set_type_name = format_ident!("{}Set", name);
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+    let bits_type = match repr_enum.variants.len() {
+        0..=8 => quote! { u8 },
+        9..=16 => quote! { u16 },
+        17..=32 => quote! { u32 },
+        33..=64 => quote! { u64 },
+        _ => quote! { u128 },
+    };
+    let ordinals = count_variants(repr_enum.variants.iter());
+
+    quote! {
+        /// A set of variants of the enum, stored as one bit per variant.
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+        #vis struct #set_type_name(#bits_type);
+
+        impl #set_type_name {
+            const ALL_BITS: #bits_type = match #bits_type::MAX.checked_shr(#bits_type::BITS - (#ordinals) as u32) {
+                ::core::option::Option::Some(bits) => bits,
+                ::core::option::Option::None => 0,
+            };
+
+            /// Returns the empty set.
+            #[inline]
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+
+            /// Returns the set of all variants.
+            #[inline]
+            pub const fn all() -> Self {
+                Self(Self::ALL_BITS)
+            }
+
+            /// Returns the raw bits of the set, where bit `n` is the variant with ordinal `n`.
+            #[inline]
+            pub const fn bits(&self) -> #bits_type {
+                self.0
+            }
+
+            /// Converts raw bits to a set.
+            /// Returns `None` if any bit doesn't belong to a variant.
+            #[inline]
+            pub const fn from_bits(bits: #bits_type) -> ::core::option::Option<Self> {
+                if bits & !Self::ALL_BITS == 0 {
+                    ::core::option::Option::Some(Self(bits))
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+
+            /// Converts raw bits to a set, dropping the bits that don't belong
+            /// to a variant.
+            #[inline]
+            pub const fn from_bits_truncate(bits: #bits_type) -> Self {
+                Self(bits & Self::ALL_BITS)
+            }
+
+            /// Returns the set with `value` added.
+            #[inline]
+            pub const fn with(self, value: #name #ty_generics) -> Self {
+                Self(self.0 | 1 << value.ordinal())
+            }
+
+            /// Returns the set with `value` removed.
+            #[inline]
+            pub const fn without(self, value: #name #ty_generics) -> Self {
+                Self(self.0 & !(1 << value.ordinal()))
+            }
+
+            /// Returns `true` if the set contains `value`.
+            #[inline]
+            pub const fn contains(&self, value: #name #ty_generics) -> bool {
+                self.0 & 1 << value.ordinal() != 0
+            }
+
+            /// Adds `value` to the set. Returns `true` if it was not present.
+            #[inline]
+            pub fn insert(&mut self, value: #name #ty_generics) -> bool {
+                let bit = 1 << value.ordinal();
+                let present = self.0 & bit != 0;
+                self.0 |= bit;
+                !present
+            }
+
+            /// Removes `value` from the set. Returns `true` if it was present.
+            #[inline]
+            pub fn remove(&mut self, value: #name #ty_generics) -> bool {
+                let bit = 1 << value.ordinal();
+                let present = self.0 & bit != 0;
+                self.0 &= !bit;
+                present
+            }
+
+            /// Returns the number of variants in the set.
+            #[inline]
+            pub const fn len(&self) -> usize {
+                self.0.count_ones() as usize
+            }
+
+            /// Returns `true` if the set is empty.
+            #[inline]
+            pub const fn is_empty(&self) -> bool {
+                self.0 == 0
+            }
+
+            /// Returns the variants in either set.
+            #[inline]
+            pub const fn union(self, other: Self) -> Self {
+                Self(self.0 | other.0)
+            }
+
+            /// Returns the variants in both sets.
+            #[inline]
+            pub const fn intersection(self, other: Self) -> Self {
+                Self(self.0 & other.0)
+            }
+
+            /// Returns the variants in `self` but not in `other`.
+            #[inline]
+            pub const fn difference(self, other: Self) -> Self {
+                Self(self.0 & !other.0)
+            }
+
+            /// Returns the variants in exactly one of the sets.
+            #[inline]
+            pub const fn symmetric_difference(self, other: Self) -> Self {
+                Self(self.0 ^ other.0)
+            }
+
+            /// Returns the variants not in the set.
+            #[inline]
+            pub const fn complement(self) -> Self {
+                Self(!self.0 & Self::ALL_BITS)
+            }
+
+            /// Returns `true` if every variant of `self` is in `other`.
+            #[inline]
+            pub const fn is_subset(&self, other: &Self) -> bool {
+                self.0 & !other.0 == 0
+            }
+
+            /// Returns `true` if every variant of `other` is in `self`.
+            #[inline]
+            pub const fn is_superset(&self, other: &Self) -> bool {
+                other.is_subset(self)
+            }
+
+            /// Returns `true` if the sets have no variant in common.
+            #[inline]
+            pub const fn is_disjoint(&self, other: &Self) -> bool {
+                self.0 & other.0 == 0
+            }
+
+            /// Returns an iterator over the variants in the set in declaration order.
+            pub fn iter(&self) -> impl ::core::iter::Iterator<Item = #name #ty_generics> + 'static {
+                let bits = self.0;
+                (0..#ordinals)
+                    .filter(move |ordinal| bits & 1 << ordinal != 0)
+                    .filter_map(#name::from_ordinal)
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for #set_type_name #where_clause {
+            #[inline]
+            fn from(value: #name #ty_generics) -> Self {
+                Self::empty().with(value)
+            }
+        }
+
+        impl ::core::ops::BitOr for #set_type_name {
+            type Output = Self;
+
+            #[inline]
+            fn bitor(self, other: Self) -> Self {
+                self.union(other)
+            }
+        }
+
+        impl ::core::ops::BitAnd for #set_type_name {
+            type Output = Self;
+
+            #[inline]
+            fn bitand(self, other: Self) -> Self {
+                self.intersection(other)
+            }
+        }
+
+        impl ::core::ops::BitXor for #set_type_name {
+            type Output = Self;
+
+            #[inline]
+            fn bitxor(self, other: Self) -> Self {
+                self.symmetric_difference(other)
+            }
+        }
+
+        impl ::core::ops::Sub for #set_type_name {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                self.difference(other)
+            }
+        }
+
+        impl ::core::ops::Not for #set_type_name {
+            type Output = Self;
+
+            #[inline]
+            fn not(self) -> Self {
+                self.complement()
+            }
+        }
+
+        impl #impl_generics ::core::iter::FromIterator<#name #ty_generics> for #set_type_name #where_clause {
+            fn from_iter<I: ::core::iter::IntoIterator<Item = #name #ty_generics>>(iter: I) -> Self {
+                let mut set = Self::empty();
+                set.extend(iter);
+                set
+            }
+        }
+
+        impl #impl_generics ::core::iter::Extend<#name #ty_generics> for #set_type_name #where_clause {
+            fn extend<I: ::core::iter::IntoIterator<Item = #name #ty_generics>>(&mut self, iter: I) {
+                for value in iter {
+                    self.insert(value);
+                }
+            }
+        }
+
+        // Prints the names of the variants in the set, e.g. `{Pending, Active}`
+        impl ::core::fmt::Debug for #set_type_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str("{")?;
+                for (i, value) in self.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_str(value.name())?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// Generate the `{Name}Map<T>` type for a `map` enum, an array with an element
+/// for every variant at the variant's ordinal:
+/// - `from_fn()`, `from_array()`, `get()` and `Index<Enum>`/`IndexMut<Enum>`
+/// - `iter()` and `iter_mut()` yielding each variant with its element
+/// - `Default`, `Clone`, `PartialEq`, `Eq`, `Hash` and `Debug` where `T` allows
+fn generate_map_impls(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let vis = &repr_enum.visibility;
+    let map_type_name = format_ident!("{}Map", name);
+    let (_, ty_generics, _) = repr_enum.generics.split_for_impl();
+    let ordinals = count_variants(repr_enum.variants.iter());
+
+    quote! {
+        /// An array with an element for every variant of the enum.
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        #vis struct #map_type_name<T>([T; #ordinals]);
+
+        impl<T> #map_type_name<T> {
+            /// Creates a map by calling `f` with every variant in declaration order.
+            pub fn from_fn(mut f: impl ::core::ops::FnMut(#name #ty_generics) -> T) -> Self {
+                Self(::core::array::from_fn(|ordinal| match #name::from_ordinal(ordinal) {
+                    ::core::option::Option::Some(key) => f(key),
+                    ::core::option::Option::None => ::core::unreachable!(),
+                }))
+            }
+
+            /// Creates a map from an array with the element of each variant at
+            /// its ordinal.
+            #[inline]
+            pub const fn from_array(values: [T; #ordinals]) -> Self {
+                Self(values)
+            }
+
+            /// Returns the elements as an array indexed by ordinal.
+            #[inline]
+            pub fn into_array(self) -> [T; #ordinals] {
+                self.0
+            }
+
+            /// Returns the elements as an array indexed by ordinal.
+            #[inline]
+            pub const fn as_array(&self) -> &[T; #ordinals] {
+                &self.0
+            }
+
+            /// Returns the element of `key`.
+            #[inline]
+            pub const fn get(&self, key: &#name #ty_generics) -> &T {
+                &self.0[key.ordinal()]
+            }
+
+            /// Returns the element of `key` mutably.
+            #[inline]
+            pub fn get_mut(&mut self, key: &#name #ty_generics) -> &mut T {
+                &mut self.0[key.ordinal()]
+            }
+
+            /// Returns an iterator over the variants and their elements in
+            /// declaration order.
+            pub fn iter(&self) -> impl ::core::iter::Iterator<Item = (#name #ty_generics, &T)> + '_ {
+                self.0.iter().enumerate().map(|(ordinal, value)| match #name::from_ordinal(ordinal) {
+                    ::core::option::Option::Some(key) => (key, value),
+                    ::core::option::Option::None => ::core::unreachable!(),
+                })
+            }
+
+            /// Returns an iterator over the variants and their mutable elements
+            /// in declaration order.
+            pub fn iter_mut(&mut self) -> impl ::core::iter::Iterator<Item = (#name #ty_generics, &mut T)> + '_ {
+                self.0.iter_mut().enumerate().map(|(ordinal, value)| match #name::from_ordinal(ordinal) {
+                    ::core::option::Option::Some(key) => (key, value),
+                    ::core::option::Option::None => ::core::unreachable!(),
+                })
+            }
+
+            /// Returns an iterator over the elements in declaration order.
+            #[inline]
+            pub fn values(&self) -> ::core::slice::Iter<'_, T> {
+                self.0.iter()
+            }
+        }
+
+        impl<T: ::core::default::Default> ::core::default::Default for #map_type_name<T> {
+            fn default() -> Self {
+                Self(::core::array::from_fn(|_| T::default()))
+            }
+        }
+
+        impl<T> ::core::ops::Index<#name #ty_generics> for #map_type_name<T> {
+            type Output = T;
+
+            #[inline]
+            fn index(&self, key: #name #ty_generics) -> &T {
+                self.get(&key)
+            }
+        }
+
+        impl<T> ::core::ops::IndexMut<#name #ty_generics> for #map_type_name<T> {
+            #[inline]
+            fn index_mut(&mut self, key: #name #ty_generics) -> &mut T {
+                self.get_mut(&key)
+            }
+        }
+
+        // Prints the elements keyed by variant name, e.g. `{Pending: 1, Active: 2}`
+        impl<T: ::core::fmt::Debug> ::core::fmt::Debug for #map_type_name<T> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut map = f.debug_map();
+                for (key, value) in self.iter() {
+                    map.key(&::core::format_args!("{}", key.name())).value(value);
+                }
+                map.finish()
+            }
+        }
+    }
+}
+
+/// Generate bit-packed storage for a `packed` enum, where each element is stored
+/// as its ordinal in `ceil(log2(n))` bits (at least one) of `u64` words, for `n`
+/// variants:
+/// - `{Name}PackedVec`, a growable vector with `push()`, `pop()`, `get()`, `set()`,
+///   iteration and conversion to and from `Vec<Enum>`
+/// - `{Name}PackedArray<WORDS>`, a fixed-size array of as many elements as fit in
+///   `WORDS` words, sized with `{Name}PackedVec::words_for(len)`
+fn generate_packed_impls(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let vis = &repr_enum.visibility;
+    let vec_type_name = format_ident!("{}PackedVec", name);
+    let array_type_name = format_ident!("{}PackedArray", name);
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+    let ordinals = count_variants(repr_enum.variants.iter());
+    // `alloc` is named through an alias of its own for each enum, as a module
+    // can't declare `extern crate alloc` twice, and `::std` is missing in
+    // `no_std` crates
+    let alloc = format_ident!("__{}_alloc", snake_case(&name.to_string()));
+
+    quote! {
+        #[doc(hidden)]
+        extern crate alloc as #alloc;
+
+        /// A vector of enum values, each stored in `ELEMENT_BITS` bits.
+        #[derive(Clone, PartialEq, Eq, Hash, Default)]
+        #vis struct #vec_type_name {
+            words: #alloc::vec::Vec<u64>,
+            len: usize,
+        }
+
+        impl #vec_type_name {
+            /// The number of bits each element is stored in.
+            pub const ELEMENT_BITS: u32 = if (#ordinals) <= 2 {
+                1
+            } else {
+                usize::BITS - ((#ordinals) - 1).leading_zeros()
+            };
+
+            /// The number of elements stored in each `u64` word.
+            pub const PER_WORD: usize = (u64::BITS / Self::ELEMENT_BITS) as usize;
+
+            const ELEMENT_MASK: u64 = u64::MAX >> (u64::BITS - Self::ELEMENT_BITS);
+
+            /// Returns the number of `u64` words needed to store `len` elements.
+            #[inline]
+            pub const fn words_for(len: usize) -> usize {
+                len.div_ceil(Self::PER_WORD)
+            }
+
+            /// Decodes the element at `index` of `words`.
+            #[inline]
+            const fn read(words: &[u64], index: usize) -> #name #ty_generics {
+                let shift = (index % Self::PER_WORD) as u32 * Self::ELEMENT_BITS;
+                let ordinal = (words[index / Self::PER_WORD] >> shift) & Self::ELEMENT_MASK;
+                match #name::from_ordinal(ordinal as usize) {
+                    ::core::option::Option::Some(value) => value,
+                    ::core::option::Option::None => ::core::unreachable!(),
+                }
+            }
+
+            /// Encodes `value` as the element at `index` of `words`.
+            #[inline]
+            const fn write(words: &mut [u64], index: usize, value: &#name #ty_generics) {
+                let shift = (index % Self::PER_WORD) as u32 * Self::ELEMENT_BITS;
+                let word = &mut words[index / Self::PER_WORD];
+                *word = (*word & !(Self::ELEMENT_MASK << shift)) | (value.ordinal() as u64) << shift;
+            }
+
+            /// Creates an empty vector.
+            #[inline]
+            pub const fn new() -> Self {
+                Self {
+                    words: #alloc::vec::Vec::new(),
+                    len: 0,
+                }
+            }
+
+            /// Creates an empty vector with space for at least `capacity` elements.
+            #[inline]
+            pub fn with_capacity(capacity: usize) -> Self {
+                Self {
+                    words: #alloc::vec::Vec::with_capacity(Self::words_for(capacity)),
+                    len: 0,
+                }
+            }
+
+            /// Returns the number of elements.
+            #[inline]
+            pub const fn len(&self) -> usize {
+                self.len
+            }
+
+            /// Returns `true` if the vector has no elements.
+            #[inline]
+            pub const fn is_empty(&self) -> bool {
+                self.len == 0
+            }
+
+            /// Appends an element.
+            pub fn push(&mut self, value: #name #ty_generics) {
+                if self.len == self.words.len() * Self::PER_WORD {
+                    self.words.push(0);
+                }
+                Self::write(&mut self.words, self.len, &value);
+                self.len += 1;
+            }
+
+            /// Removes the last element and returns it, or `None` if the vector is empty.
+            pub fn pop(&mut self) -> ::core::option::Option<#name #ty_generics> {
+                if self.len == 0 {
+                    return ::core::option::Option::None;
+                }
+                self.len -= 1;
+                let value = Self::read(&self.words, self.len);
+                // Unused bits are kept zero so that equal vectors have equal words
+                let shift = (self.len % Self::PER_WORD) as u32 * Self::ELEMENT_BITS;
+                self.words[self.len / Self::PER_WORD] &= !(Self::ELEMENT_MASK << shift);
+                self.words.truncate(Self::words_for(self.len));
+                ::core::option::Option::Some(value)
+            }
+
+            /// Returns the element at `index`, or `None` if it is out of bounds.
+            #[inline]
+            pub fn get(&self, index: usize) -> ::core::option::Option<#name #ty_generics> {
+                if index < self.len {
+                    ::core::option::Option::Some(Self::read(&self.words, index))
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+
+            /// Replaces the element at `index`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is out of bounds.
+            #[inline]
+            pub fn set(&mut self, index: usize, value: #name #ty_generics) {
+                ::core::assert!(
+                    index < self.len,
+                    "index out of bounds: the len is {} but the index is {}",
+                    self.len,
+                    index
+                );
+                Self::write(&mut self.words, index, &value);
+            }
+
+            /// Removes all elements.
+            #[inline]
+            pub fn clear(&mut self) {
+                self.words.clear();
+                self.len = 0;
+            }
+
+            /// Returns an iterator over the elements.
+            pub fn iter(&self) -> impl ::core::iter::Iterator<Item = #name #ty_generics> + '_ {
+                (0..self.len).map(|index| Self::read(&self.words, index))
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#alloc::vec::Vec<#name #ty_generics>> for #vec_type_name #where_clause {
+            fn from(values: #alloc::vec::Vec<#name #ty_generics>) -> Self {
+                values.into_iter().collect()
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#vec_type_name> for #alloc::vec::Vec<#name #ty_generics> #where_clause {
+            fn from(values: #vec_type_name) -> Self {
+                values.iter().collect()
+            }
+        }
+
+        impl #impl_generics ::core::iter::FromIterator<#name #ty_generics> for #vec_type_name #where_clause {
+            fn from_iter<I: ::core::iter::IntoIterator<Item = #name #ty_generics>>(iter: I) -> Self {
+                let mut values = Self::new();
+                values.extend(iter);
+                values
+            }
+        }
+
+        impl #impl_generics ::core::iter::Extend<#name #ty_generics> for #vec_type_name #where_clause {
+            fn extend<I: ::core::iter::IntoIterator<Item = #name #ty_generics>>(&mut self, iter: I) {
+                for value in iter {
+                    self.push(value);
+                }
+            }
+        }
+
+        // Prints the names of the elements, e.g. `[Pending, Active]`
+        impl ::core::fmt::Debug for #vec_type_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut list = f.debug_list();
+                for value in self.iter() {
+                    list.entry(&::core::format_args!("{}", value.name()));
+                }
+                list.finish()
+            }
+        }
+
+        /// A fixed-size array of enum values packed into `WORDS` words, each
+        /// value stored in `ELEMENT_BITS` bits.
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        #vis struct #array_type_name<const WORDS: usize>([u64; WORDS]);
+
+        impl<const WORDS: usize> #array_type_name<WORDS> {
+            /// The number of elements, every element that fits in `WORDS` words.
+            pub const LEN: usize = WORDS * #vec_type_name::PER_WORD;
+
+            /// Creates an array with every element set to `value`.
+            pub const fn new(value: #name #ty_generics) -> Self {
+                let mut word = 0;
+                let mut index = 0;
+                while index < #vec_type_name::PER_WORD {
+                    #vec_type_name::write(::core::slice::from_mut(&mut word), index, &value);
+                    index += 1;
+                }
+                Self([word; WORDS])
+            }
+
+            /// Returns the number of elements, `LEN`.
+            #[inline]
+            pub const fn len(&self) -> usize {
+                Self::LEN
+            }
+
+            /// Returns `true` if the array has no elements.
+            #[inline]
+            pub const fn is_empty(&self) -> bool {
+                Self::LEN == 0
+            }
+
+            /// Returns the element at `index`, or `None` if it is out of bounds.
+            #[inline]
+            pub const fn get(&self, index: usize) -> ::core::option::Option<#name #ty_generics> {
+                if index < Self::LEN {
+                    ::core::option::Option::Some(#vec_type_name::read(&self.0, index))
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+
+            /// Replaces the element at `index`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is out of bounds.
+            #[inline]
+            pub const fn set(&mut self, index: usize, value: #name #ty_generics) {
+                ::core::assert!(index < Self::LEN, "index out of bounds");
+                #vec_type_name::write(&mut self.0, index, &value);
+            }
+
+            /// Returns an iterator over the elements.
+            pub fn iter(&self) -> impl ::core::iter::Iterator<Item = #name #ty_generics> + '_ {
+                (0..Self::LEN).map(|index| #vec_type_name::read(&self.0, index))
+            }
+        }
+
+        // Prints the names of the elements, e.g. `[Pending, Active]`
+        impl<const WORDS: usize> ::core::fmt::Debug for #array_type_name<WORDS> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut list = f.debug_list();
+                for value in self.iter() {
+                    list.entry(&::core::format_args!("{}", value.name()));
+                }
+                list.finish()
+            }
+        }
+    }
+}
+
+/// Generate the `Atomic{Name}` wrapper for an `atomic` enum, backed by the
+/// atomic integer matching the repr type and gated on `target_has_atomic`:
+/// - `new()`, `load()`, `store()`, `swap()` and `into_inner()`
+/// - `compare_exchange()`, `compare_exchange_weak()` and `fetch_update()`
+fn generate_atomic_impls(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let allow_deprecated = allow_deprecated(repr_enum);
+    let repr_type = &repr_enum.repr_type;
+    let vis = &repr_enum.visibility;
+    let atomic_type_name = format_ident!("Atomic{}", name);
+    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+    let int_type = IntType::from_ident(repr_type).expect("repr type is validated during parsing");
+    let atomic_int = format_ident!("{}", int_type.atomic_name());
+    let width = int_type.atomic_width();
+    let ordering = quote! { ::core::sync::atomic::Ordering };
+
+    // Only discriminants are ever stored, including those of skipped variants
+    let stored_checks = repr_enum.variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let discriminant = variant_value(repr_enum, v);
+        let cfg = &v.cfg;
+        quote! {
+            #(#cfg)*
+            if value == #discriminant {
+                return #name::#variant_name;
+            };
+        }
+    });
+
+    let stored_fallback = match &repr_enum.options.other {
+        Some(other) => quote! { #name::#other(value) },
+        None => quote! { ::core::unreachable!() },
+    };
+
+    quote! {
+        /// An enum value that can be shared between threads, stored as its
+        /// integer representation.
+        #[cfg(target_has_atomic = #width)]
+        #[repr(transparent)]
+        #vis struct #atomic_type_name(::core::sync::atomic::#atomic_int);
+
+        #[cfg(target_has_atomic = #width)]
+        #allow_deprecated
+        impl #atomic_type_name {
+            /// Decodes a value that was stored by this type.
+            #[inline]
+            const fn from_stored(value: #repr_type) -> #name #ty_generics {
+                #(#stored_checks)*
+                #stored_fallback
+            }
+
+            /// Creates a new atomic enum value.
+            #[inline]
+            pub const fn new(value: #name #ty_generics) -> Self {
+                Self(::core::sync::atomic::#atomic_int::new(value.as_repr()))
+            }
+
+            /// Loads the value.
+            #[inline]
+            pub fn load(&self, order: #ordering) -> #name #ty_generics {
+                Self::from_stored(self.0.load(order))
+            }
+
+            /// Stores `value`.
+            #[inline]
+            pub fn store(&self, value: #name #ty_generics, order: #ordering) {
+                self.0.store(value.as_repr(), order);
+            }
+
+            /// Stores `value`, returning the previous value.
+            #[inline]
+            pub fn swap(&self, value: #name #ty_generics, order: #ordering) -> #name #ty_generics {
+                Self::from_stored(self.0.swap(value.as_repr(), order))
+            }
+
+            /// Stores `new` if the current value is `current`. Returns the previous
+            /// value, as `Ok` if it was `current` and as `Err` otherwise.
+            #[inline]
+            pub fn compare_exchange(
+                &self,
+                current: #name #ty_generics,
+                new: #name #ty_generics,
+                success: #ordering,
+                failure: #ordering,
+            ) -> ::core::result::Result<#name #ty_generics, #name #ty_generics> {
+                self.0
+                    .compare_exchange(current.as_repr(), new.as_repr(), success, failure)
+                    .map(Self::from_stored)
+                    .map_err(Self::from_stored)
+            }
+
+            /// Like `compare_exchange()`, but may fail spuriously.
+            #[inline]
+            pub fn compare_exchange_weak(
+                &self,
+                current: #name #ty_generics,
+                new: #name #ty_generics,
+                success: #ordering,
+                failure: #ordering,
+            ) -> ::core::result::Result<#name #ty_generics, #name #ty_generics> {
+                self.0
+                    .compare_exchange_weak(current.as_repr(), new.as_repr(), success, failure)
+                    .map(Self::from_stored)
+                    .map_err(Self::from_stored)
+            }
+
+            /// Replaces the value with the result of `f` until it succeeds or `f`
+            /// returns `None`. Returns the previous value, as `Ok` if it was
+            /// replaced and as `Err` otherwise.
+            #[inline]
+            pub fn fetch_update(
+                &self,
+                set_order: #ordering,
+                fetch_order: #ordering,
+                mut f: impl ::core::ops::FnMut(#name #ty_generics) -> ::core::option::Option<#name #ty_generics>,
+            ) -> ::core::result::Result<#name #ty_generics, #name #ty_generics> {
+                self.0
+                    .fetch_update(set_order, fetch_order, |value| {
+                        f(Self::from_stored(value)).map(|new| new.as_repr())
+                    })
+                    .map(Self::from_stored)
+                    .map_err(Self::from_stored)
+            }
+
+            /// Consumes the atomic and returns the value.
+            #[inline]
+            pub const fn into_inner(self) -> #name #ty_generics {
+                Self::from_stored(self.0.into_inner())
+            }
+        }
+
+        #[cfg(target_has_atomic = #width)]
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for #atomic_type_name #where_clause {
+            #[inline]
+            fn from(value: #name #ty_generics) -> Self {
+                Self::new(value)
+            }
+        }
+
+        // Prints the name of the current value, e.g. `AtomicStatus(Active)`
+        #[cfg(target_has_atomic = #width)]
+        impl ::core::fmt::Debug for #atomic_type_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_tuple(::core::stringify!(#atomic_type_name))
+                    .field(&::core::format_args!("{}", self.load(#ordering::Relaxed).name()))
+                    .finish()
+            }
+        }
+    }
+}
+
+/// Generate accessors for the halves of a `compose` enum, named after the
+/// sub-enums in snake case:
+/// - `category()` and `code()` for `compose(hi = Category, lo = Code)`
+/// - `from_parts()`, the variant made of two halves if there is one
+/// - Compile-time assertions that both halves of every discriminant are
+///   variants of the sub-enums
+fn generate_compose_impls(repr_enum: &ReprEnum, compose: &Compose) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let repr_type = &repr_enum.repr_type;
+    let allow_deprecated = allow_deprecated(repr_enum);
+    let int_type = IntType::from_ident(repr_type).expect("repr type is validated during parsing");
+    let shift = proc_macro2::Literal::u32_unsuffixed(int_type.bits() / 2);
+    let lo_mask = quote! { (((1 as #repr_type) << #shift) - 1) };
+    let (hi, lo) = (&compose.hi, &compose.lo);
+    let accessor = |path: &syn::Path| {
+        let ty = &path.segments.last().expect("a path has a segment").ident;
+        format_ident!("{}", snake_case(&ty.to_string()))
+    };
+    let (hi_fn, lo_fn) = (accessor(hi), accessor(lo));
+    let (hi_name, lo_name) = (path_to_string(hi), path_to_string(lo));
+    let hi_doc = format!("Returns the high half of the value as a [`{}`].", hi_name);
+    let lo_doc = format!("Returns the low half of the value as a [`{}`].", lo_name);
+
+    let hi_checks = discriminant_checks(
+        repr_enum,
+        |discriminant| quote! { #hi::from_repr((#discriminant >> #shift) as _).is_some() },
+        |variant_name| format!("high half of `{}` is not a variant of `{}`", variant_name, hi_name),
+    );
+    let lo_checks = discriminant_checks(
+        repr_enum,
+        |discriminant| quote! { #lo::from_repr((#discriminant & #lo_mask) as _).is_some() },
+        |variant_name| format!("low half of `{}` is not a variant of `{}`", variant_name, lo_name),
+    );
+
+    // Parts written as a `(Hi, Lo)` pair must come back out of their halves,
+    // rather than be truncated into another variant
+    let fit_checks = compose.parts.iter().filter_map(|(variant_name, hi_part, lo_part)| {
+        let v = repr_enum.variants.iter().find(|v| v.name == *variant_name)?;
+        let discriminant = variant_value(repr_enum, v);
+        let bits = int_type.bits() / 2;
+        let hi_message = format!("high part of `{}` doesn't fit in {} bits", variant_name, bits);
+        let lo_message = format!("low part of `{}` doesn't fit in {} bits", variant_name, bits);
+        let cfg = &v.cfg;
+        Some(quote_spanned! {variant_name.span()=>
+            #(#cfg)*
+            ::core::assert!(
+                match #hi::from_repr((#discriminant >> #shift) as _) {
+                    ::core::option::Option::Some(part) => part.as_repr() == #hi_part.as_repr(),
+                    ::core::option::Option::None => false,
+                },
+                #hi_message
+            );
+            #(#cfg)*
+            ::core::assert!(
+                match #lo::from_repr((#discriminant & #lo_mask) as _) {
+                    ::core::option::Option::Some(part) => part.as_repr() == #lo_part.as_repr(),
+                    ::core::option::Option::None => false,
+                },
+                #lo_message
+            );
+        })
+    });
+
+    quote! {
+        #allow_deprecated
+        const _: () = {
+            #(#hi_checks)*
+            #(#lo_checks)*
+            #(#fit_checks)*
+        };
+
+        impl #name {
+            #[doc = #hi_doc]
+            #[inline]
+            pub const fn #hi_fn(&self) -> #hi {
+                match #hi::from_repr((self.as_repr() >> #shift) as _) {
+                    ::core::option::Option::Some(part) => part,
+                    ::core::option::Option::None => ::core::unreachable!(),
+                }
+            }
+
+            #[doc = #lo_doc]
+            #[inline]
+            pub const fn #lo_fn(&self) -> #lo {
+                match #lo::from_repr((self.as_repr() & #lo_mask) as _) {
+                    ::core::option::Option::Some(part) => part,
+                    ::core::option::Option::None => ::core::unreachable!(),
+                }
+            }
+
+            /// Returns the variant made of the two halves, or `None` if there is none.
+            #[inline]
+            pub const fn from_parts(#hi_fn: #hi, #lo_fn: #lo) -> ::core::option::Option<Self> {
+                let value = ((#hi_fn.as_repr() as #repr_type) << #shift)
+                    | ((#lo_fn.as_repr() as #repr_type) & #lo_mask);
+                match Self::from_repr(value) {
+                    // Parts that don't fit in their half are not truncated into another variant
+                    ::core::option::Option::Some(variant)
+                        if variant.#hi_fn().as_repr() == #hi_fn.as_repr()
+                            && variant.#lo_fn().as_repr() == #lo_fn.as_repr() =>
+                    {
+                        ::core::option::Option::Some(variant)
+                    }
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    }
+}
+
+/// Convert a type name such as `ErrorCategory` to snake case (`error_category`),
+/// keeping acronyms together (`IOError` becomes `io_error`).
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+/// Generate the binary codec of a `codec` enum, writing the tag in the repr
+/// type followed by the fields in declaration order:
+/// - `encode()`, appending the bytes to a buffer
+/// - `decode()`, returning the value and the number of bytes read
+/// - `{Name}DecodeError`, telling an unknown tag from a buffer that is too short
+///   or a field value that is invalid
+/// - The `WireEncode` and `WireDecode` traits, private to the generated code and
+///   implemented for the fixed-width integers, `f32`, `f64`, `bool`, `char` and
+///   byte arrays
+fn generate_codec_impls(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+    let name = &repr_enum.name;
+    let repr_type = &repr_enum.repr_type;
+    let vis = &repr_enum.visibility;
+    let allow_deprecated = allow_deprecated(repr_enum);
+    let error_type_name = format_ident!("{}DecodeError", name);
+    let (to_bytes, from_bytes) = if repr_enum.options.codec_big_endian {
+        (quote! { to_be_bytes }, quote! { from_be_bytes })
+    } else {
+        (quote! { to_le_bytes }, quote! { from_le_bytes })
+    };
+
+    // Enums with fields decode their tag through the tag enum
+    let (tag_name, tag) = if repr_enum.variants.iter().any(EnumVariant::has_fields) {
+        (format_ident!("{}Tag", name), quote! { self.tag() })
+    } else {
+        (name.clone(), quote! { self.as_repr() })
+    };
+
+    let encode_arms = repr_enum.variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let cfg = &v.cfg;
+        // Named fields are bound to the same names as tuple fields, so they
+        // can't shadow `buf`
+        let bindings: Vec<_> = (0..v.fields.len()).map(|i| format_ident!("field{}", i)).collect();
+        let pattern = match &v.fields {
+            syn::Fields::Named(fields) => {
+                let names = fields.named.iter().map(|field| &field.ident);
+                quote! { Self::#variant_name { #(#names: #bindings),* } }
+            }
+            syn::Fields::Unnamed(_) => quote! { Self::#variant_name(#(#bindings),*) },
+            syn::Fields::Unit => quote! { Self::#variant_name },
+        };
+        quote! {
+            #(#cfg)*
+            #pattern => {
+                #(WireEncode::encode_to(#bindings, buf);)*
+            }
+        }
+    });
+
+    let decode_arms = repr_enum.variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let cfg = &v.cfg;
+        let read = quote! { WireDecode::decode_from(bytes, &mut offset)? };
+        let value = match &v.fields {
+            syn::Fields::Named(fields) => {
+                let names = fields.named.iter().map(|field| &field.ident);
+                quote! { Self::#variant_name { #(#names: #read),* } }
+            }
+            syn::Fields::Unnamed(fields) => {
+                let reads = fields.unnamed.iter().map(|_| &read);
+                quote! { Self::#variant_name(#(#reads),*) }
+            }
+            syn::Fields::Unit => quote! { Self::#variant_name },
+        };
+        quote! {
+            #(#cfg)*
+            ::core::option::Option::Some(#tag_name::#variant_name) => #value,
+        }
+    });
+
+    // Skipped variants are left out of `from_repr` but still encoded, so they
+    // are matched on their own. The `mask` applies to the tag as in `from_repr_masked`.
+    let (lookup, masked_tag) = if repr_enum.options.mask.is_some() {
+        (
+            quote! { #tag_name::from_repr_masked(tag) },
+            quote! { tag & #tag_name::DECODE_MASK },
+        )
+    } else {
+        (quote! { #tag_name::from_repr(tag) }, quote! { tag })
+    };
+    let skipped: Vec<_> = repr_enum.variants.iter().filter(|v| v.options.skip).collect();
+    let variant_mut = if skipped.is_empty() { quote! {} } else { quote! { mut } };
+    let skipped_lookups = skipped.iter().map(|v| {
+        let variant_name = &v.name;
+        let cfg = &v.cfg;
+        quote! {
+            #(#cfg)*
+            if variant.is_none() && #masked_tag == #tag_name::#variant_name as #repr_type {
+                variant = ::core::option::Option::Some(#tag_name::#variant_name);
+            }
+        }
+    });
+
+    let primitives = [
+        "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64",
+    ]
+    .map(|ty| format_ident!("{}", ty));
+
+    quote! {
+        /// Error type returned when decoding this enum from bytes fails.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis enum #error_type_name {
+            /// The tag doesn't match any variant.
+            UnknownTag(#repr_type),
+            /// The buffer ends before the tag or a field.
+            UnexpectedEnd,
+            /// A `bool` or `char` field holds a value that isn't valid for its type.
+            InvalidValue,
+        }
+
+        impl ::core::fmt::Display for #error_type_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    Self::UnknownTag(tag) => write!(f, "unknown {} tag: {}", stringify!(#name), tag),
+                    Self::UnexpectedEnd => write!(f, "unexpected end of {} bytes", stringify!(#name)),
+                    Self::InvalidValue => write!(f, "invalid field value in {} bytes", stringify!(#name)),
+                }
             }
         }
-    });
 
-    let as_repr_arms = repr_enum.variants.iter().map(|v| {
-        let variant_name = &v.name;
-        quote! {
-            #name::#variant_name => #name::#variant_name as #repr_type,
-        }
-    });
+        impl ::core::error::Error for #error_type_name {}
 
-    quote! {
-        impl #impl_generics #name #ty_generics #where_clause {
-            /// Converts an integer value to the enum variant.
-            /// Returns `None` if the value doesn't match any variant.
-            #[inline]
-            pub const fn from_repr(value: #repr_type) -> ::core::option::Option<Self> {
-                #(#from_repr_checks)*
-                ::core::option::Option::None
+        const _: () = {
+            /// A field type that can be written by `encode`.
+            trait WireEncode {
+                fn encode_to(&self, buf: &mut impl ::core::iter::Extend<u8>);
             }
 
-            /// Converts the enum variant to its integer representation.
+            /// A field type that can be read by `decode`.
+            trait WireDecode: ::core::marker::Sized {
+                /// Reads the value at `*offset`, advancing the offset past it.
+                fn decode_from(bytes: &[u8], offset: &mut usize) -> ::core::result::Result<Self, #error_type_name>;
+            }
+
+            /// Reads the `N` bytes at `*offset`, advancing the offset past them.
             #[inline]
-            pub const fn as_repr(&self) -> #repr_type {
-                match self {
-                    #(#as_repr_arms)*
+            fn take<const N: usize>(bytes: &[u8], offset: &mut usize) -> ::core::result::Result<[u8; N], #error_type_name> {
+                let value = offset
+                    .checked_add(N)
+                    .and_then(|end| bytes.get(*offset..end))
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or(#error_type_name::UnexpectedEnd)?;
+                *offset += N;
+                ::core::result::Result::Ok(value)
+            }
+
+            #(
+                impl WireEncode for #primitives {
+                    #[inline]
+                    fn encode_to(&self, buf: &mut impl ::core::iter::Extend<u8>) {
+                        buf.extend(self.#to_bytes());
+                    }
+                }
+
+                impl WireDecode for #primitives {
+                    #[inline]
+                    fn decode_from(bytes: &[u8], offset: &mut usize) -> ::core::result::Result<Self, #error_type_name> {
+                        take(bytes, offset).map(Self::#from_bytes)
+                    }
+                }
+            )*
+
+            // `bool` is one byte, 0 or 1
+            impl WireEncode for bool {
+                #[inline]
+                fn encode_to(&self, buf: &mut impl ::core::iter::Extend<u8>) {
+                    WireEncode::encode_to(&u8::from(*self), buf);
                 }
             }
-        }
-    }
-}
 
-/// Generate the From<Enum> for T trait implementations.
-/// Generates both owned and reference implementations:
-/// - `From<Enum> for T` - converts owned enum to integer
-/// - `From<&Enum> for T` - converts enum reference to integer
-fn generate_from_impl(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
-    let name = &repr_enum.name;
-    let repr_type = &repr_enum.repr_type;
-    let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
+            impl WireDecode for bool {
+                #[inline]
+                fn decode_from(bytes: &[u8], offset: &mut usize) -> ::core::result::Result<Self, #error_type_name> {
+                    match u8::decode_from(bytes, offset)? {
+                        0 => ::core::result::Result::Ok(false),
+                        1 => ::core::result::Result::Ok(true),
+                        _ => ::core::result::Result::Err(#error_type_name::InvalidValue),
+                    }
+                }
+            }
 
-    quote! {
-        // Convert owned enum to integer
-        impl #impl_generics ::core::convert::From<#name #ty_generics> for #repr_type #where_clause {
-            #[inline]
-            fn from(value: #name #ty_generics) -> Self {
-                value.as_repr()
+            // `char` is its scalar value as a `u32`
+            impl WireEncode for char {
+                #[inline]
+                fn encode_to(&self, buf: &mut impl ::core::iter::Extend<u8>) {
+                    WireEncode::encode_to(&u32::from(*self), buf);
+                }
             }
-        }
 
-        // Convert enum reference to integer
-        impl #impl_generics ::core::convert::From<&#name #ty_generics> for #repr_type #where_clause {
-            #[inline]
-            fn from(value: &#name #ty_generics) -> Self {
-                value.as_repr()
+            impl WireDecode for char {
+                #[inline]
+                fn decode_from(bytes: &[u8], offset: &mut usize) -> ::core::result::Result<Self, #error_type_name> {
+                    char::from_u32(u32::decode_from(bytes, offset)?).ok_or(#error_type_name::InvalidValue)
+                }
             }
-        }
+
+            impl<const N: usize> WireEncode for [u8; N] {
+                #[inline]
+                fn encode_to(&self, buf: &mut impl ::core::iter::Extend<u8>) {
+                    buf.extend(self.iter().copied());
+                }
+            }
+
+            impl<const N: usize> WireDecode for [u8; N] {
+                #[inline]
+                fn decode_from(bytes: &[u8], offset: &mut usize) -> ::core::result::Result<Self, #error_type_name> {
+                    take(bytes, offset)
+                }
+            }
+
+            #allow_deprecated
+            impl #name {
+                /// Appends the tag of the variant, in the repr type, followed by its
+                /// fields in declaration order.
+                pub fn encode(&self, buf: &mut impl ::core::iter::Extend<u8>) {
+                    WireEncode::encode_to(&#tag, buf);
+                    match self {
+                        #(#encode_arms)*
+                    }
+                }
+
+                /// Reads a value written by `encode()` from the start of `bytes`,
+                /// returning it with the number of bytes read.
+                pub fn decode(bytes: &[u8]) -> ::core::result::Result<(Self, usize), #error_type_name> {
+                    let mut offset = 0;
+                    let tag: #repr_type = WireDecode::decode_from(bytes, &mut offset)?;
+                    let #variant_mut variant = #lookup;
+                    #(#skipped_lookups)*
+                    let value = match variant {
+                        #(#decode_arms)*
+                        ::core::option::Option::None => {
+                            return ::core::result::Result::Err(#error_type_name::UnknownTag(tag))
+                        }
+                    };
+                    ::core::result::Result::Ok((value, offset))
+                }
+            }
+        };
     }
 }
 
-/// Generate the TryFrom<T> for Enum trait implementations.
-/// Generates both owned and reference implementations:
-/// - `TryFrom<T> for Enum` - converts owned integer to enum
-/// - `TryFrom<&T> for Enum` - converts integer reference to enum
-fn generate_try_from_impl(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
+/// Generate char conversions for an `ascii` enum:
+/// - `as_char()` and `from_char()` methods
+/// - `TryFrom<char> for Enum`, with its own error type
+/// - `Display`, printing the character
+/// - A compile-time assertion that every discriminant is ASCII
+fn generate_ascii_impls(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
     let name = &repr_enum.name;
+    let allow_deprecated = allow_deprecated(repr_enum);
     let repr_type = &repr_enum.repr_type;
-    let error_type_name = format_ident!("{}ConversionError", name);
+    let vis = &repr_enum.visibility;
+    let char_error_type_name = format_ident!("{}CharConversionError", name);
     let (impl_generics, ty_generics, where_clause) = repr_enum.generics.split_for_impl();
 
+    let ascii_checks = discriminant_checks(
+        repr_enum,
+        |discriminant| quote! { (#discriminant).is_ascii() },
+        |variant_name| format!("discriminant of `{}` is not an ASCII character", variant_name),
+    );
+
     quote! {
-        // Convert owned integer to enum
-        impl #impl_generics ::core::convert::TryFrom<#repr_type> for #name #ty_generics #where_clause {
-            type Error = #error_type_name;
+        #allow_deprecated
+        const _: () = {
+            #(#ascii_checks)*
+        };
 
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Converts the enum variant to its ASCII character.
             #[inline]
-            fn try_from(value: #repr_type) -> ::core::result::Result<Self, Self::Error> {
-                Self::from_repr(value).ok_or(#error_type_name(value))
+            pub const fn as_char(&self) -> char {
+                self.as_repr() as char
+            }
+
+            /// Converts an ASCII character to the enum variant.
+            /// Returns `None` if the character doesn't match any variant.
+            #[inline]
+            pub const fn from_char(value: char) -> ::core::option::Option<Self> {
+                if value.is_ascii() {
+                    Self::from_repr(value as #repr_type)
+                } else {
+                    ::core::option::Option::None
+                }
             }
         }
 
-        // Convert integer reference to enum
-        impl #impl_generics ::core::convert::TryFrom<&#repr_type> for #name #ty_generics #where_clause {
-            type Error = #error_type_name;
+        // Convert character to enum
+        impl #impl_generics ::core::convert::TryFrom<char> for #name #ty_generics #where_clause {
+            type Error = #char_error_type_name;
 
             #[inline]
-            fn try_from(value: &#repr_type) -> ::core::result::Result<Self, Self::Error> {
-                Self::from_repr(*value).ok_or(#error_type_name(*value))
+            fn try_from(value: char) -> ::core::result::Result<Self, Self::Error> {
+                Self::from_char(value).ok_or(#char_error_type_name(value))
             }
         }
-    }
-}
 
-/// Generate the error type for failed conversions.
-fn generate_error_type(repr_enum: &ReprEnum) -> proc_macro2::TokenStream {
-    let name = &repr_enum.name;
-    let repr_type = &repr_enum.repr_type;
-    let vis = &repr_enum.visibility;
-    let error_type_name = format_ident!("{}ConversionError", name);
+        impl #impl_generics ::core::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.as_char(), f)
+            }
+        }
 
-    quote! {
-        /// Error type returned when trying to convert an integer to this enum
-        /// but the value doesn't match any known variant.
+        /// Error type returned when trying to convert a character to this enum
+        /// but the character doesn't match any known variant.
         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-        #vis struct #error_type_name(pub #repr_type);
+        #vis struct #char_error_type_name(pub char);
 
-        impl ::core::fmt::Display for #error_type_name {
+        impl ::core::fmt::Display for #char_error_type_name {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                write!(f, "unknown {} variant: {}", stringify!(#name), self.0)
+                write!(f, "unknown {} character: {:?}", stringify!(#name), self.0)
             }
         }
 
-        impl ::core::error::Error for #error_type_name {}
+        impl ::core::error::Error for #char_error_type_name {}
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::repr_enum::{CalculatedDiscriminant, EnumVariant};
+    use crate::repr_enum::{AcceptedValues, CalculatedDiscriminant, Newtype};
     use syn::parse_quote;
 
     fn create_simple_repr_enum() -> ReprEnum {
@@ -204,17 +3105,26 @@ mod tests {
             visibility: parse_quote! { pub },
             attributes: vec![],
             generics: Default::default(),
+            options: Default::default(),
             variants: vec![
                 EnumVariant {
                     name: parse_quote! { Pending },
+                    fields: syn::Fields::Unit,
                     attributes: vec![],
                     discriminant: Some(parse_quote! { 0 }),
+                    value: Some(0),
+                    cfg: vec![],
+                    options: Default::default(),
                     calculated_discriminant: CalculatedDiscriminant::Explicit(parse_quote! { 0 }),
                 },
                 EnumVariant {
                     name: parse_quote! { Active },
+                    fields: syn::Fields::Unit,
                     attributes: vec![],
                     discriminant: Some(parse_quote! { 1 }),
+                    value: Some(1),
+                    cfg: vec![],
+                    options: Default::default(),
                     calculated_discriminant: CalculatedDiscriminant::Explicit(parse_quote! { 1 }),
                 },
             ],
@@ -247,6 +3157,107 @@ mod tests {
         assert!(output_str.contains("return :: core :: option :: Option :: Some (Status :: Pending)"));
     }
 
+    #[test]
+    fn test_generate_accepted_values() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.variants[0].options.accepts = vec![AcceptedValues {
+            source: parse_quote! { 0x10 },
+            start: Some(parse_quote! { 0x10 }),
+            end: Some(parse_quote! { 0x10 }),
+            bounds: Some((0x10, 0x10)),
+        }];
+        repr_enum.variants[1].options.accepts = vec![AcceptedValues {
+            source: parse_quote! { LOW.. },
+            start: Some(parse_quote! { LOW }),
+            end: None,
+            bounds: None,
+        }];
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        // Aliases and ranges come after the discriminants
+        let canonical = output_str.find("if value == Status :: Active as u8").unwrap();
+        let alias = output_str.find("if value == (0x10) { return :: core :: option :: Option :: Some (Status :: Pending) ; }").unwrap();
+        assert!(canonical < alias);
+        assert!(output_str.contains("if value >= (LOW) { return :: core :: option :: Option :: Some (Status :: Active) ; }"));
+        // Only the range of unknown bounds needs a compile-time overlap check
+        assert!(output_str.contains("! ((LOW) <= (Status :: Pending as u8))"));
+        assert!(output_str.contains("! ((LOW) <= (0x10))"));
+        assert!(!output_str.contains("(0x10) <= (Status :: Active as u8)"));
+    }
+
+    #[test]
+    fn test_generate_variant_info() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.variants[0].options.rename = Some(parse_quote! { "pending" });
+        repr_enum.variants[1].options.hidden = true;
+
+        let output_str = generate_variant_info(&repr_enum).to_string();
+        assert!(output_str.contains("pub const COUNT : usize = 1usize"));
+        assert!(output_str.contains("pub const VARIANTS : & 'static [Self] = & [Status :: Pending]"));
+        assert!(output_str.contains("Status :: Pending => \"pending\""));
+        assert!(output_str.contains("Status :: Active => \"Active\""));
+        assert!(output_str.contains("Status :: Active => 1usize"));
+        assert!(output_str.contains("if ordinal == 1usize"));
+    }
+
+    #[test]
+    fn test_generate_cfg_variants() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.variants[1].cfg = vec![parse_quote! { #[cfg(feature = "x")] }];
+        repr_enum.options.variants = true;
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("# [cfg (feature = \"x\")] if value == Status :: Active as u8"));
+        assert!(output_str.contains("# [cfg (feature = \"x\")] Status :: Active => Status :: Active as u8"));
+        assert!(output_str.contains("# [cfg (feature = \"x\")] Status :: Active => \"Active\""));
+        assert!(output_str.contains("# [cfg (feature = \"x\")] { count += 1 ; } ;"));
+        assert!(output_str.contains("# [cfg (feature = \"x\")] { array [index] = Status :: Active ;"));
+        assert!(output_str.contains("let array = [const { Status :: Pending } ; LEN] ;"));
+    }
+
+    #[test]
+    fn test_generate_deprecated_variants() {
+        let mut repr_enum = create_simple_repr_enum();
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(!output_str.contains("is_deprecated"));
+
+        repr_enum.options.variants = true;
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(!output_str.contains("allow (deprecated)"));
+        assert!(output_str.contains("Status :: Active => false"));
+
+        repr_enum.variants[1].attributes = vec![parse_quote! { #[deprecated] }];
+        repr_enum.options.strict = true;
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("# [allow (deprecated)] impl Status"));
+        assert!(output_str.contains("Status :: Active => true"));
+        assert!(output_str.contains("pub const fn from_repr_strict"));
+        assert!(output_str.contains("Self :: from_repr_strict (value) . ok_or"));
+    }
+
+    #[test]
+    fn test_generate_auto_repr_alias() {
+        let mut repr_enum = create_simple_repr_enum();
+        let output_str = generate_enum_definition(&repr_enum).to_string();
+        assert!(!output_str.contains("StatusRepr"));
+
+        repr_enum.options.auto = true;
+        let output_str = generate_enum_definition(&repr_enum).to_string();
+        assert!(output_str.contains("pub type StatusRepr = u8 ;"));
+    }
+
+    #[test]
+    fn test_generate_skipped_variant() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.variants[1].options.skip = true;
+
+        let output_str = generate_impl_methods(&repr_enum).to_string();
+        assert!(output_str.contains("if value == Status :: Pending as u8"));
+        assert!(!output_str.contains("if value == Status :: Active as u8"));
+        // Still encoded as usual
+        assert!(output_str.contains("Status :: Active => Status :: Active as u8"));
+    }
+
     #[test]
     fn test_generate_from_impl() {
         let repr_enum = create_simple_repr_enum();
@@ -291,9 +3302,9 @@ mod tests {
         let output = generate_error_type(&repr_enum);
         let output_str = output.to_string();
 
-        assert!(output_str.contains("pub struct StatusConversionError"));
-        assert!(output_str.contains("impl :: core :: fmt :: Display"));
-        assert!(output_str.contains("impl :: core :: error :: Error"));
+        assert!(output_str.contains("pub struct StatusConversionError (pub u8)"));
+        assert!(output_str.contains("impl :: core :: fmt :: Display for StatusConversionError"));
+        assert!(output_str.contains("impl :: core :: error :: Error for StatusConversionError"));
     }
 
     #[test]
@@ -320,17 +3331,26 @@ mod tests {
             visibility: parse_quote! {},
             attributes: vec![],
             generics: Default::default(),
+            options: Default::default(),
             variants: vec![
                 EnumVariant {
                     name: parse_quote! { Red },
+                    fields: syn::Fields::Unit,
                     attributes: vec![],
                     discriminant: None,
+                    value: Some(0),
+                    cfg: vec![],
+                    options: Default::default(),
                     calculated_discriminant: CalculatedDiscriminant::Implicit(0),
                 },
                 EnumVariant {
                     name: parse_quote! { Green },
+                    fields: syn::Fields::Unit,
                     attributes: vec![],
                     discriminant: None,
+                    value: Some(1),
+                    cfg: vec![],
+                    options: Default::default(),
                     calculated_discriminant: CalculatedDiscriminant::Implicit(1),
                 },
             ],
@@ -379,6 +3399,386 @@ mod tests {
         assert!(output_str.contains("# [derive (Debug)]"));
     }
 
+    #[test]
+    fn test_generate_newtype_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.newtype = Some(Newtype {
+            ty: parse_quote! { Opcode },
+            new: None,
+            get: None,
+        });
+
+        let from_str = generate_from_impl(&repr_enum).to_string();
+        assert!(from_str.contains("impl :: core :: convert :: From < Status > for Opcode"));
+        assert!(from_str.contains("impl :: core :: convert :: From < & Status > for Opcode"));
+        assert!(from_str.contains("Opcode (value . as_repr ())"));
+
+        let try_from_str = generate_try_from_impl(&repr_enum).to_string();
+        assert!(try_from_str.contains("TryFrom < Opcode > for Status"));
+        assert!(try_from_str.contains("let value : u8 = value . 0 ;"));
+
+        // User-declared accessors
+        repr_enum.options.newtype = Some(Newtype {
+            ty: parse_quote! { Opcode },
+            new: Some(parse_quote! { new }),
+            get: Some(parse_quote! { get }),
+        });
+        let from_str = generate_from_impl(&repr_enum).to_string();
+        assert!(from_str.contains("Opcode :: new (value . as_repr ())"));
+        let try_from_str = generate_try_from_impl(&repr_enum).to_string();
+        assert!(try_from_str.contains("let value : u8 = value . get () ;"));
+    }
+
+    #[test]
+    fn test_generate_also_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.also = vec![parse_quote! { u32 }, parse_quote! { i8 }];
+
+        let output = generate_also_impls(&repr_enum);
+        let output_str = output.to_string();
+
+        // u8 -> u32 is lossless
+        assert!(output_str.contains("impl :: core :: convert :: From < Status > for u32"));
+        assert!(output_str.contains("impl :: core :: convert :: From < & Status > for u32"));
+        assert!(output_str.contains("TryFrom < u32 > for Status"));
+        assert!(output_str.contains("type Error = StatusIntConversionError < u32 >"));
+        assert!(output_str.contains("pub struct StatusIntConversionError < T > (pub T)"));
+
+        // u8 -> i8 is not
+        assert!(output_str.contains("impl :: core :: convert :: TryFrom < Status > for i8"));
+        assert!(output_str.contains("impl :: core :: convert :: TryFrom < & Status > for i8"));
+        assert!(!output_str.contains("convert :: From < Status > for i8"));
+        assert!(output_str.contains("TryFrom < i8 > for Status"));
+
+        // Nothing is generated without the option
+        let output = generate_also_impls(&create_simple_repr_enum());
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn test_generate_nonzero_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.nonzero = true;
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("pub const fn as_nonzero (& self) -> :: core :: num :: NonZeroU8"));
+        assert!(output_str.contains("From < Status > for :: core :: num :: NonZeroU8"));
+        assert!(output_str.contains("TryFrom < :: core :: num :: NonZeroU8 > for Status"));
+        assert!(output_str.contains("Status :: Pending as u8 != 0"));
+        assert!(output_str.contains("size_of :: < :: core :: option :: Option < Status >"));
+
+        // Not generated without the option
+        let output_str = expand_repr_cast(&create_simple_repr_enum()).to_string();
+        assert!(!output_str.contains("NonZeroU8"));
+    }
+
+    #[test]
+    fn test_generate_sentinel_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.none = Some(parse_quote! { 0xFF });
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("pub const NONE_SENTINEL : u8 = 0xFF"));
+        assert!(output_str.contains("pub const fn encode_option"));
+        assert!(output_str.contains("pub const fn decode_option"));
+        assert!(output_str.contains("Status :: Active as u8 != Status :: NONE_SENTINEL"));
+
+        // Not generated without the option
+        let output_str = expand_repr_cast(&create_simple_repr_enum()).to_string();
+        assert!(!output_str.contains("NONE_SENTINEL"));
+    }
+
+    #[test]
+    fn test_generate_bits_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.bits = Some(parse_quote! { 3 });
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("pub const BITS : u32 = 3"));
+        assert!(output_str.contains("pub const MASK : u8 = 7"));
+        assert!(output_str.contains("pub const fn pack_into (& self , word : u32 , shift : u32) -> u32"));
+        assert!(output_str.contains("pub const fn unpack_from"));
+        assert!(output_str.contains("Status :: Active as u8 & ! Status :: MASK == 0"));
+
+        // Not generated without the option
+        let output_str = expand_repr_cast(&create_simple_repr_enum()).to_string();
+        assert!(!output_str.contains("pack_into"));
+    }
+
+    #[test]
+    fn test_generate_mask_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.mask = Some(parse_quote! { 0x0F });
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("pub const DECODE_MASK : u8 = 0x0F"));
+        assert!(output_str.contains("pub const fn from_repr_masked"));
+        assert!(output_str.contains("Status :: Active as u8 & ! Status :: DECODE_MASK == 0"));
+        // TryFrom is unaffected unless requested
+        assert!(output_str.contains("Self :: from_repr (value) . ok_or"));
+
+        repr_enum.options.mask_try_from = true;
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("Self :: from_repr_masked (value) . ok_or"));
+        assert!(output_str.contains("Self :: from_repr_masked (* value) . ok_or"));
+    }
+
+    #[test]
+    fn test_generate_reserved_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.reserved = vec![parse_quote! { 0x10..=0x1F }, parse_quote! { 0xFF }];
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("pub enum StatusConversionError {"));
+        assert!(output_str.contains("Reserved (u8)"));
+        assert!(output_str.contains(
+            "pub const fn is_reserved (value : u8) -> bool { (value >= (0x10) && value <= (0x1F)) || value == (0xFF) }"
+        ));
+        assert!(output_str.contains("! Status :: is_reserved (Status :: Active as u8)"));
+        assert!(output_str.contains("StatusConversionError :: Reserved (value)"));
+        assert!(!output_str.contains("pub struct StatusConversionError"));
+    }
+
+    #[test]
+    fn test_generate_flags_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.flags = true;
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("pub struct StatusFlags (u8)"));
+        assert!(output_str.contains("(Status :: Active as u8) . count_ones () == 1"));
+        assert!(output_str.contains("bits |= Status :: Active as u8 ;"));
+        assert!(output_str.contains("pub const fn from_bits (bits : u8)"));
+        assert!(output_str.contains("pub const fn from_bits_truncate (bits : u8) -> Self"));
+        assert!(output_str.contains("BitOr for Status"));
+        assert!(output_str.contains("Not for StatusFlags"));
+
+        // Not generated without the option
+        let output_str = expand_repr_cast(&create_simple_repr_enum()).to_string();
+        assert!(!output_str.contains("StatusFlags"));
+    }
+
+    #[test]
+    fn test_generate_set_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.set = true;
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("pub struct StatusSet (u8)"));
+        assert!(output_str.contains("pub const fn contains (& self , value : Status) -> bool"));
+        assert!(output_str.contains("u8 :: MAX . checked_shr (u8 :: BITS - (2usize) as u32)"));
+        assert!(output_str.contains("pub const fn with (self , value : Status) -> Self"));
+        assert!(output_str.contains("Sub for StatusSet"));
+
+        // The backing type holds a bit for every variant
+        repr_enum.variants = (0..9)
+            .map(|i| EnumVariant {
+                name: format_ident!("V{}", i as u32),
+                fields: syn::Fields::Unit,
+                attributes: vec![],
+                cfg: vec![],
+                discriminant: None,
+                value: Some(i),
+                options: Default::default(),
+                calculated_discriminant: CalculatedDiscriminant::Implicit(i),
+            })
+            .collect();
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("pub struct StatusSet (u16)"));
+
+        // Not generated without the option
+        let output_str = expand_repr_cast(&create_simple_repr_enum()).to_string();
+        assert!(!output_str.contains("StatusSet"));
+    }
+
+    #[test]
+    fn test_generate_map_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.map = true;
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("pub struct StatusMap < T > ([T ; 2usize])"));
+        assert!(output_str.contains("pub fn from_fn (mut f : impl :: core :: ops :: FnMut (Status) -> T) -> Self"));
+        assert!(output_str.contains("Index < Status > for StatusMap < T >"));
+        assert!(output_str.contains("& self . 0 [key . ordinal ()]"));
+
+        // Not generated without the option
+        let output_str = expand_repr_cast(&create_simple_repr_enum()).to_string();
+        assert!(!output_str.contains("StatusMap"));
+    }
+
+    #[test]
+    fn test_generate_packed_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.packed = true;
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("extern crate alloc as __status_alloc ;"));
+        assert!(output_str.contains("pub struct StatusPackedVec { words : __status_alloc :: vec :: Vec < u64 > , len : usize , }"));
+        assert!(output_str.contains("pub struct StatusPackedArray < const WORDS : usize > ([u64 ; WORDS])"));
+        assert!(output_str.contains("usize :: BITS - ((2usize) - 1) . leading_zeros ()"));
+        assert!(output_str.contains("From < __status_alloc :: vec :: Vec < Status >"));
+
+        // Not generated without the option
+        let output_str = expand_repr_cast(&create_simple_repr_enum()).to_string();
+        assert!(!output_str.contains("StatusPackedVec"));
+    }
+
+    #[test]
+    fn test_generate_atomic_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.atomic = true;
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains(
+            "# [cfg (target_has_atomic = \"8\")] # [repr (transparent)] pub struct AtomicStatus (:: core :: sync :: atomic :: AtomicU8)"
+        ));
+        assert!(output_str.contains("if value == Status :: Active as u8 { return Status :: Active ; }"));
+        assert!(output_str.contains("pub fn swap (& self , value : Status"));
+        assert!(output_str.contains("pub const fn into_inner (self) -> Status"));
+
+        // Not generated without the option
+        let output_str = expand_repr_cast(&create_simple_repr_enum()).to_string();
+        assert!(!output_str.contains("AtomicStatus"));
+    }
+
+    #[test]
+    fn test_generate_open_newtype() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.open = true;
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("pub enum StatusKnown"));
+        assert!(output_str.contains("TryFrom < u8 > for StatusKnown"));
+        assert!(output_str.contains("pub struct Status (pub u8) ;"));
+        assert!(output_str.contains("pub const Active : Self = Self (StatusKnown :: Active as u8) ;"));
+        assert!(output_str.contains("pub const fn known (self) -> :: core :: option :: Option < StatusKnown >"));
+        assert!(output_str.contains("From < u8 > for Status"));
+        assert!(!output_str.contains("pub enum Status {"));
+    }
+
+    #[test]
+    fn test_generate_tagged_enum() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.variants[1].fields = syn::Fields::Unnamed(parse_quote! { (u32) });
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("# [repr (u8)] pub enum Status { Pending = 0 , Active (u32) = 1 , }"));
+        assert!(output_str.contains("pub enum StatusTag { Pending = 0 , Active = 1 , }"));
+        assert!(output_str.contains("Self :: Active { .. } => StatusTag :: Active ,"));
+        assert!(output_str.contains("pub const fn tag (& self) -> u8"));
+        assert!(output_str.contains("From < & Status > for StatusTag"));
+        assert!(output_str.contains("TryFrom < u8 > for StatusTag"));
+        assert!(!output_str.contains("TryFrom < u8 > for Status "));
+    }
+
+    #[test]
+    fn test_generate_codec_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.codec = true;
+        repr_enum.variants[1].fields = syn::Fields::Named(parse_quote! { { id: u32, key: [u8; 4] } });
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("pub enum StatusDecodeError"));
+        assert!(output_str.contains("UnknownTag (u8) ,"));
+        assert!(output_str.contains("WireEncode :: encode_to (& self . tag () , buf) ;"));
+        assert!(output_str.contains("Self :: Active { id : field0 , key : field1 } => { WireEncode :: encode_to (field0 , buf) ; WireEncode :: encode_to (field1 , buf) ; }"));
+        assert!(output_str.contains("let variant = StatusTag :: from_repr (tag) ;"));
+        assert!(output_str.contains("Self :: Active { id : WireDecode :: decode_from (bytes , & mut offset) ? , key : WireDecode :: decode_from (bytes , & mut offset) ? }"));
+        assert!(output_str.contains("impl WireDecode for bool"));
+        assert!(output_str.contains("impl WireDecode for char"));
+        assert!(output_str.contains("self . to_le_bytes ()"));
+        // The tag enum doesn't get a codec of its own
+        assert!(!output_str.contains("StatusTagDecodeError"));
+
+        repr_enum.options.codec_big_endian = true;
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("self . to_be_bytes ()"));
+        assert!(output_str.contains("Self :: from_be_bytes"));
+    }
+
+    #[test]
+    fn test_generate_compose_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.repr_type = parse_quote! { u16 };
+        repr_enum.options.compose = Some(Compose {
+            hi: parse_quote! { ErrorCategory },
+            lo: parse_quote! { codes::Code },
+            parts: vec![],
+        });
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("pub const fn error_category (& self) -> ErrorCategory"));
+        assert!(output_str.contains("pub const fn code (& self) -> codes :: Code"));
+        assert!(output_str.contains("ErrorCategory :: from_repr ((self . as_repr () >> 8) as _)"));
+        assert!(output_str.contains("codes :: Code :: from_repr ((self . as_repr () & (((1 as u16) << 8) - 1)) as _)"));
+        assert!(output_str.contains(
+            "pub const fn from_parts (error_category : ErrorCategory , code : codes :: Code) -> :: core :: option :: Option < Self >"
+        ));
+        assert!(output_str.contains("\"high half of `Active` is not a variant of `ErrorCategory`\""));
+        assert!(output_str.contains("\"low half of `Active` is not a variant of `codes::Code`\""));
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("Code"), "code");
+        assert_eq!(snake_case("ErrorCategory"), "error_category");
+        assert_eq!(snake_case("IOError"), "io_error");
+        assert_eq!(snake_case("Http2Status"), "http2_status");
+    }
+
+    #[test]
+    fn test_generate_other_variant() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.other = Some(parse_quote! { Unknown });
+
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("# [repr (u8)] pub enum Status { Pending = 0 , Active = 1 , # [doc = "));
+        assert!(output_str.contains("Unknown (u8) = 2 , }"));
+        assert!(!output_str.contains(" as u8"));
+
+        // Without known discriminants `Unknown` follows the last variant
+        repr_enum.variants[1].value = None;
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("Unknown (u8) , }"));
+        assert!(output_str.contains("if value == :: core :: convert :: identity :: < u8 > (1)"));
+        assert!(output_str.contains("Status :: Unknown (value) => * value ,"));
+        assert!(output_str.contains("From < u8 > for Status"));
+        assert!(output_str.contains(":: core :: option :: Option :: None => Self :: Unknown (value) ,"));
+        assert!(!output_str.contains("TryFrom < u8 > for Status"));
+
+        // A variant after a const expression is compared against that expression plus its offset
+        repr_enum.variants[0].discriminant = Some(parse_quote! { BASE });
+        repr_enum.variants[0].value = None;
+        repr_enum.variants[0].calculated_discriminant = CalculatedDiscriminant::Explicit(parse_quote! { BASE });
+        repr_enum.variants[1].discriminant = None;
+        repr_enum.variants[1].calculated_discriminant =
+            CalculatedDiscriminant::Relative { base: parse_quote! { BASE }, offset: 1 };
+        let output_str = expand_repr_cast(&repr_enum).to_string();
+        assert!(output_str.contains("if value == :: core :: convert :: identity :: < u8 > (BASE)"));
+        assert!(output_str.contains("if value == :: core :: convert :: identity :: < u8 > ((BASE) + 1)"));
+    }
+
+    #[test]
+    fn test_generate_ascii_impls() {
+        let mut repr_enum = create_simple_repr_enum();
+        repr_enum.options.ascii = true;
+
+        let output = expand_repr_cast(&repr_enum);
+        let output_str = output.to_string();
+
+        assert!(output_str.contains("pub const fn as_char"));
+        assert!(output_str.contains("pub const fn from_char"));
+        assert!(output_str.contains("TryFrom < char > for Status"));
+        assert!(output_str.contains("impl :: core :: fmt :: Display for Status"));
+        assert!(output_str.contains("pub struct StatusCharConversionError (pub char)"));
+        assert!(output_str.contains("(Status :: Pending as u8) . is_ascii ()"));
+
+        // Not generated without the option
+        let output_str = expand_repr_cast(&create_simple_repr_enum()).to_string();
+        assert!(!output_str.contains("as_char"));
+    }
+
     #[test]
     fn test_signed_repr_type() {
         let mut repr_enum = create_simple_repr_enum();
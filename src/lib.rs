@@ -1,7 +1,7 @@
 //! # repr_cast
 //!
-//! A procedural macro for fieldless enums that generates conversions between
-//! enum variants and their integer representation types.
+//! A procedural macro for enums that generates conversions between enum
+//! variants and their integer representation types.
 //!
 //! ## Architecture
 //!
@@ -19,12 +19,13 @@
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput, Error, Ident};
 
+mod eval;
 mod expand;
 mod parse;
 mod repr_enum;
 
-/// An attribute macro for fieldless enums that generates conversions between
-/// the enum and its integer representation type.
+/// An attribute macro for enums that generates conversions between the enum
+/// and its integer representation type.
 ///
 /// # Example
 ///
@@ -46,9 +47,101 @@ mod repr_enum;
 /// - `Status::as_repr(&self) -> u8` - convert enum to integer
 /// - `StatusConversionError` - error type for failed conversions
 ///
+/// # Options
+///
+/// Options follow the repr type, e.g. `#[repr_cast(u8, ascii)]`. The repr type
+/// may be omitted if the enum already has a `#[repr(T)]` attribute.
+///
+/// - `variants` - `name()`, `is_deprecated()`, `COUNT`, `VARIANTS` and `iter()` (the variants
+///   in declaration order, without hidden variants), `ordinal()` and `from_ordinal()`. Also
+///   generated for `strict`, `flags`, `set`, `map`, `packed`, `atomic`, `open` and enums with
+///   `rename` or `hidden` variants.
+/// - `auto` / `auto_signed` in place of the repr type - picks the smallest unsigned (or
+///   signed) type holding every discriminant, named by a generated `{Name}Repr` alias.
+///   Every discriminant must be evaluable by the macro.
+/// - `also(u16, u32, ...)` - conversions to and from additional integer types:
+///   `From<Enum>` when lossless (otherwise `TryFrom<Enum>`) and `TryFrom<U> for Enum`
+///   with a `{Name}IntConversionError<U>` carrying the original value.
+/// - `newtype = Opcode` - `From<Enum> for Opcode` and `TryFrom<Opcode> for Enum` for a
+///   tuple struct around the repr type. Use `newtype(Opcode, new = from_raw, get = raw)`
+///   to go through `Opcode::from_raw(value)` and `opcode.raw()` instead of the field.
+/// - `nonzero` - `as_nonzero()`, `From<Enum> for NonZeroT` and `TryFrom<NonZeroT>`, with a
+///   compile-time check that no discriminant is zero. These are generated without the
+///   option when every discriminant is known and none is zero, unless `nonzero = false`.
+/// - `none = 0xFF` - `encode_option()` and `decode_option()` for `Option<Enum>`, with the
+///   sentinel encoding `None`. The sentinel must not collide with any discriminant.
+/// - `bits = 3` - `BITS`, `MASK`, `pack_into()` and `unpack_from()` for storing the enum in
+///   a few bits of a `u32` word. Every discriminant must fit in `bits`.
+/// - `mask = 0x0F` - `DECODE_MASK` and `from_repr_masked()`, which ignores the bits outside
+///   the mask. Every discriminant must lie within the mask. `mask(0x0F, try_from)` applies
+///   the mask in the `TryFrom` conversions as well.
+/// - `reserved(0x10..=0x1F, 0xFF)` - values reserved for future use. The error type becomes
+///   an enum of `Reserved(value)` and `Unknown(value)`, and `is_reserved()` is generated.
+///   No discriminant may be reserved.
+/// - `strict` - `from_repr_strict()`, which rejects `#[deprecated]` variants, and used by
+///   the `TryFrom` conversions.
+/// - `flags` - for enums whose discriminants are single bits, checked at compile time:
+///   a `{Name}Flags` set type with `from_bits()`, `from_bits_truncate()`, `bits()`,
+///   `contains()`, `insert()`, `remove()`, `iter()` and the `BitOr`, `BitAnd` and `Not`
+///   operators. `Variant | Variant` builds a set.
+/// - `set` - a `{Name}Set` of variants with one bit per variant, indexed by `ordinal()`:
+///   const construction with `empty()`, `with()` and `without()`, set algebra, iteration
+///   in declaration order and `bits()` / `from_bits()`. Enums may have up to 128 variants.
+/// - `map` - a `{Name}Map<T>` wrapping an array with an element per variant: `from_fn()`,
+///   `Index<Enum>`/`IndexMut<Enum>` and `iter()` yielding `(variant, &element)`.
+/// - `packed` - `{Name}PackedVec`, storing each element in `ceil(log2(n))` bits for `n`
+///   variants, with `push()`, `get()`, `set()`, iteration and conversions to and from
+///   `Vec<Enum>`, and
+///   `{Name}PackedArray<WORDS>`, a fixed-size array sized with `{Name}PackedVec::words_for(len)`.
+/// - `atomic` - `Atomic{Name}`, backed by the matching atomic integer and gated on
+///   `target_has_atomic`, with `new()`, `load()`, `store()`, `swap()`, `compare_exchange()`,
+///   `fetch_update()` and `into_inner()` typed in the enum. Not available for 128-bit types.
+/// - `open` - the enum is renamed `{Name}Known` and `{Name}` becomes a newtype around the
+///   repr type that preserves unknown values, with a const per variant, `known()`, a
+///   `Debug`/`Display` printing the name or `Unknown(n)` and infallible `From` conversions.
+///   Derives must be written after `#[repr_cast(...)]`, where they apply to `{Name}Known`.
+/// - `other = Unknown` - adds an `Unknown(T)` variant holding the values that match no
+///   other variant. `From<T> for Enum` replaces `TryFrom<T>` and `as_repr()` returns the
+///   stored value. Derives must be written after `#[repr_cast(...)]` to see the new variant.
+/// - `codec` - `encode()` and `decode()` writing the tag in the repr type followed by the
+///   fields, each an integer, `f32`, `f64`, `bool`, `char` or `[u8; N]`, in little-endian
+///   byte order (`codec(big_endian)` for big-endian). `decode()` returns the value and the
+///   number of bytes read, or a `{Name}DecodeError` of `UnknownTag(tag)`, `UnexpectedEnd`
+///   or `InvalidValue` for a `bool` or `char` field holding an invalid value.
+/// - `compose(hi = Category, lo = Code)` - discriminants made of a high and a low half, each
+///   a variant of a repr_cast enum, written as `(Io, Missing)` or any other expression:
+///   `category()`, `code()` and `from_parts()`, with a compile-time check that both halves
+///   of every discriminant are variants.
+/// - `ascii` - for `u8` enums with ASCII discriminants: generates `as_char()`,
+///   `from_char()`, `TryFrom<char>` and a `Display` that prints the character.
+///   Char literals (`Get = 'G'`) are accepted as discriminants of any enum.
+///
+/// # Variant options
+///
+/// Variants accept a `#[repr_cast(...)]` attribute of their own:
+///
+/// - `skip` - the variant is never produced by `from_repr()` or `TryFrom`.
+/// - `rename = "..."` - the string returned by `name()`.
+/// - `hidden` - the variant is left out of `VARIANTS`, `iter()` and `COUNT`.
+/// - `alias = 0` - `from_repr()` and `TryFrom` also accept this value for the variant,
+///   while `as_repr()` keeps returning the discriminant. May be repeated.
+/// - `range = 200..=299` - likewise for a range of values; open ranges extend to the
+///   limits of the repr type.
+///
+/// The values accepted for a variant must not overlap those of any other variant.
+///
+/// Variants behind `#[cfg(...)]` are supported: the generated code referring to a
+/// variant carries its `cfg`, and `COUNT` and `VARIANTS` only include enabled variants.
+///
+/// # Enums with fields
+///
+/// Variants may carry fields. The enum gets `#[repr(T)]`, `tag(&self) -> T` and
+/// `kind(&self) -> StatusTag`, and a fieldless `StatusTag` enum with the same variants
+/// and discriminants gets everything above, including the options, as well as
+/// `From<&Status>`. `open` and `other` require a fieldless enum.
+///
 /// # Requirements
 ///
-/// - The enum must be fieldless (all variants must be unit variants)
 /// - All discriminant values must fit in the specified integer type
 ///
 /// # Supported Integer Types
@@ -60,10 +153,15 @@ mod repr_enum;
 pub fn repr_cast(args: TokenStream, input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    // Parse the repr type from the attribute arguments
-    let repr_type: Ident = if args.is_empty() {
-        // If no args provided, try to extract from existing #[repr(...)] attribute
-        match parse::extract_repr_from_attrs(&input) {
+    // Parse the repr type and options from the attribute arguments
+    let mut args_parsed = parse::ReprCastArgs::default();
+    let args_parser = syn::meta::parser(|meta| args_parsed.parse(meta));
+    parse_macro_input!(args with args_parser);
+
+    let repr_type: Ident = match args_parsed.repr_type {
+        Some(ty) => ty,
+        // If no repr type provided, try to extract from existing #[repr(...)] attribute
+        None => match parse::extract_repr_from_attrs(&input) {
             Ok(Some(ty)) => ty,
             Ok(None) => {
                 return Error::new_spanned(
@@ -72,13 +170,11 @@ pub fn repr_cast(args: TokenStream, input: TokenStream) -> TokenStream {
                 ).to_compile_error().into();
             }
             Err(e) => return e.to_compile_error().into(),
-        }
-    } else {
-        parse_macro_input!(args as Ident)
+        },
     };
 
     // Pipeline: parse → expand
-    let repr_enum = match parse::parse_repr_cast(repr_type, input) {
+    let repr_enum = match parse::parse_repr_cast(repr_type, args_parsed.options, input) {
         Ok(repr_enum) => repr_enum,
         Err(err) => return err.to_compile_error().into(),
     };
@@ -1,24 +1,200 @@
 //! Parsing logic for repr_cast macro.
 //!
 //! This module is responsible for:
-//! - Parsing the repr type from the attribute arguments
-//! - Validating the enum structure (must be fieldless)
+//! - Parsing the repr type and options from the attribute arguments
+//! - Validating the enum structure (fieldless, unless the options allow fields)
 //! - Extracting enum metadata (name, visibility, attributes, variants)
 //! - Computing discriminant values for variants
+//!
+//! The `validate_*` functions only check the discriminants and option values
+//! known at expansion time. The rest are checked by compile-time assertions in
+//! the generated code.
+
+use crate::eval;
+use crate::repr_enum::{
+    AcceptedValues, CalculatedDiscriminant, Compose, EnumVariant, IntType, Newtype, ReprEnum,
+    ReprOptions, VariantOptions,
+};
+use syn::meta::ParseNestedMeta;
+use syn::parse::Parse;
+use syn::{Attribute, Data, DeriveInput, Error, Expr, Fields, Ident, Lit, Meta, Result, Token};
+
+/// The arguments of the `#[repr_cast(...)]` attribute.
+///
+/// The repr type comes first and may be omitted when the enum already has a
+/// `#[repr(T)]` attribute; it is followed by comma-separated options.
+#[derive(Debug, Default)]
+pub struct ReprCastArgs {
+    /// The repr type, if given explicitly
+    pub repr_type: Option<Ident>,
+    /// Options following the repr type
+    pub options: ReprOptions,
+    /// Whether any argument has been parsed yet
+    seen_any: bool,
+}
+
+impl ReprCastArgs {
+    /// Parse a single argument. Intended for use with `syn::meta::parser`.
+    pub fn parse(&mut self, meta: ParseNestedMeta) -> Result<()> {
+        let first = !self.seen_any;
+        self.seen_any = true;
+
+        if meta.path.is_ident("ascii") {
+            self.options.ascii = true;
+            Ok(())
+        } else if meta.path.is_ident("also") {
+            meta.parse_nested_meta(|inner| match inner.path.get_ident() {
+                Some(ident) => {
+                    self.options.also.push(ident.clone());
+                    Ok(())
+                }
+                None => Err(inner.error("expected an integer type")),
+            })
+        } else if meta.path.is_ident("none") {
+            self.options.none = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("bits") {
+            self.options.bits = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("reserved") {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            let entries = content.parse_terminated(Expr::parse, Token![,])?;
+            self.options.reserved.extend(entries);
+            Ok(())
+        } else if meta.path.is_ident("mask") {
+            self.parse_mask(&meta)
+        } else if meta.path.is_ident("strict") {
+            self.options.strict = true;
+            Ok(())
+        } else if meta.path.is_ident("flags") {
+            self.options.flags = true;
+            Ok(())
+        } else if meta.path.is_ident("set") {
+            self.options.set = true;
+            Ok(())
+        } else if meta.path.is_ident("map") {
+            self.options.map = true;
+            Ok(())
+        } else if meta.path.is_ident("packed") {
+            self.options.packed = true;
+            Ok(())
+        } else if meta.path.is_ident("atomic") {
+            self.options.atomic = true;
+            Ok(())
+        } else if meta.path.is_ident("open") {
+            self.options.open = true;
+            Ok(())
+        } else if meta.path.is_ident("other") {
+            self.options.other = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("codec") {
+            self.parse_codec(&meta)
+        } else if meta.path.is_ident("compose") {
+            self.options.compose = Some(parse_compose(&meta)?);
+            Ok(())
+        } else if meta.path.is_ident("variants") {
+            self.options.variants = true;
+            Ok(())
+        } else if meta.path.is_ident("nonzero") {
+            // `nonzero = false` turns the detection of enums without a zero off
+            let enabled = if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<syn::LitBool>()?.value
+            } else {
+                true
+            };
+            self.options.nonzero = enabled;
+            self.options.nonzero_disabled = !enabled;
+            Ok(())
+        } else if meta.path.is_ident("newtype") {
+            self.options.newtype = Some(parse_newtype(&meta)?);
+            Ok(())
+        } else if let (true, Some(ident)) = (first, meta.path.get_ident()) {
+            self.repr_type = Some(ident.clone());
+            Ok(())
+        } else {
+            Err(meta.error(format!(
+                "unknown repr_cast option `{}`",
+                path_to_string(&meta.path)
+            )))
+        }
+    }
+
+    /// Parse the `mask` option, either `mask = 0x0F` or `mask(0x0F, try_from)`.
+    fn parse_mask(&mut self, meta: &ParseNestedMeta) -> Result<()> {
+        if meta.input.peek(Token![=]) {
+            self.options.mask = Some(meta.value()?.parse()?);
+            return Ok(());
+        }
+
+        let content;
+        syn::parenthesized!(content in meta.input);
+        self.options.mask = Some(content.parse()?);
+        while !content.is_empty() {
+            content.parse::<Token![,]>()?;
+            if content.is_empty() {
+                break;
+            }
+            let flag: Ident = content.parse()?;
+            if flag == "try_from" {
+                self.options.mask_try_from = true;
+            } else {
+                return Err(Error::new_spanned(
+                    &flag,
+                    format!("unknown mask option `{}`, expected `try_from`", flag),
+                ));
+            }
+        }
+        Ok(())
+    }
 
-use crate::repr_enum::{CalculatedDiscriminant, EnumVariant, ReprEnum};
-use syn::{Data, DeriveInput, Error, Fields, Ident, Meta, Result};
+    /// Parse the `codec` option, either `codec` or `codec(big_endian)`.
+    fn parse_codec(&mut self, meta: &ParseNestedMeta) -> Result<()> {
+        self.options.codec = true;
+        if !meta.input.peek(syn::token::Paren) {
+            return Ok(());
+        }
+        meta.parse_nested_meta(|inner| {
+            if inner.path.is_ident("big_endian") {
+                self.options.codec_big_endian = true;
+                Ok(())
+            } else {
+                Err(inner.error(format!(
+                    "unknown codec option `{}`, expected `big_endian`",
+                    path_to_string(&inner.path)
+                )))
+            }
+        })
+    }
+}
 
 /// Parse the repr_cast macro input.
 ///
 /// # Arguments
-/// * `args` - The attribute arguments (the repr type)
+/// * `repr_type` - The repr type from the attribute arguments or `#[repr(...)]`
+/// * `options` - The options from the attribute arguments
 /// * `input` - The enum definition
 ///
 /// # Returns
 /// A validated `ReprEnum` ready for code generation, or an error if validation fails.
-pub fn parse_repr_cast(args: Ident, input: DeriveInput) -> Result<ReprEnum> {
-    let repr_type = args;
+pub fn parse_repr_cast(
+    repr_type: Ident,
+    mut options: ReprOptions,
+    input: DeriveInput,
+) -> Result<ReprEnum> {
+    // `auto` and `auto_signed` are resolved once the discriminants are known
+    let auto_signed = match repr_type.to_string().as_str() {
+        "auto" => Some(false),
+        "auto_signed" => Some(true),
+        _ => None,
+    };
+    let int_type = IntType::from_ident(&repr_type);
+    if int_type.is_none() && auto_signed.is_none() {
+        return Err(Error::new_spanned(
+            &repr_type,
+            "repr_cast requires a primitive integer repr type (u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128 or isize), or `auto`",
+        ));
+    }
 
     // Validate that we're working with an enum
     let enum_data = match &input.data {
@@ -31,18 +207,108 @@ pub fn parse_repr_cast(args: Ident, input: DeriveInput) -> Result<ReprEnum> {
         }
     };
 
-    // Validate all variants are fieldless
-    for variant in &enum_data.variants {
-        if !matches!(variant.fields, Fields::Unit) {
-            return Err(Error::new_spanned(
-                variant,
-                "repr_cast can only be applied to fieldless enums (enums with unit variants)",
-            ));
+    // Discriminants of a composed enum may be written as `(Hi, Lo)`
+    let mut enum_variants = enum_data.variants.clone();
+    if let Some(compose) = &mut options.compose {
+        let int_type = validate_compose(&repr_type, int_type)?;
+        for variant in &mut enum_variants {
+            if let Some((hi, lo)) = compose_discriminant(variant, compose, int_type)? {
+                compose.parts.push((variant.ident.clone(), hi, lo));
+            }
+        }
+    }
+
+    // Enums with fields get their conversions through a generated tag enum,
+    // except with the options that change the shape of the enum itself
+    let fieldless_option = [("open", options.open), ("other", options.other.is_some())]
+        .into_iter()
+        .find(|(_, enabled)| *enabled);
+    if let Some((option, _)) = fieldless_option {
+        for variant in &enum_variants {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(Error::new_spanned(
+                    variant,
+                    format!(
+                        "the `{}` option can only be applied to fieldless enums (enums with unit variants)",
+                        option
+                    ),
+                ));
+            }
         }
     }
 
     // Calculate discriminants for all variants
-    let variants = calculate_discriminants(&enum_data.variants)?;
+    let (int_type, variants) = match (int_type, auto_signed) {
+        (Some(int_type), _) => (int_type, calculate_discriminants(&enum_variants, int_type)?),
+        (None, signed) => {
+            options.auto = true;
+            select_auto_repr(&repr_type, signed == Some(true), &enum_variants)?
+        }
+    };
+    let repr_type = Ident::new(int_type.name(), repr_type.span());
+    validate_accepted_values(&variants)?;
+
+    if options.ascii {
+        validate_ascii(&repr_type, int_type, &variants)?;
+    }
+    validate_also(int_type, &options.also)?;
+    if let Some(sentinel) = &options.none {
+        validate_sentinel(sentinel, int_type, &variants)?;
+    }
+    if let Some(bits) = &options.bits {
+        validate_bits(bits, int_type, &variants)?;
+    }
+    if let Some(mask) = &options.mask {
+        validate_mask(mask, int_type, &variants)?;
+    }
+    if !options.reserved.is_empty() {
+        validate_reserved(&options.reserved, int_type, &variants)?;
+    }
+    if options.flags {
+        validate_flags(int_type, &variants)?;
+    }
+    if options.atomic && int_type.bits() == 128 {
+        return Err(Error::new_spanned(
+            &repr_type,
+            format!(
+                "the `atomic` option is not supported for `{}`, which has no stable atomic type",
+                repr_type
+            ),
+        ));
+    }
+    if options.set && variants.len() > 128 {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "the `set` option supports enums of at most 128 variants",
+        ));
+    }
+    if let Some(other) = &options.other {
+        validate_other(other, &options, &variants)?;
+    }
+    if options.codec {
+        validate_codec(&repr_type, int_type, &input.generics)?;
+    }
+    if options.nonzero {
+        validate_nonzero(&variants)?;
+    } else if options.other.is_some() || options.nonzero_disabled {
+        // The `other` variant may hold zero, and `nonzero = false` opts out
+    } else {
+        // Enums that are known to have no zero discriminant get NonZero conversions too
+        options.nonzero = !variants.is_empty()
+            && variants.iter().all(|v| v.value.is_some_and(|value| value != 0));
+    }
+    // The variant lists and names are generated on request, and for the
+    // options and variant options built on them
+    options.variants |= options.strict
+        || options.flags
+        || options.set
+        || options.map
+        || options.packed
+        || options.atomic
+        || options.open
+        || variants
+            .iter()
+            .any(|v| v.options.rename.is_some() || v.options.hidden);
 
     // Filter out repr attributes that match our repr_type to avoid duplication
     let attributes = input
@@ -58,9 +324,469 @@ pub fn parse_repr_cast(args: Ident, input: DeriveInput) -> Result<ReprEnum> {
         attributes,
         generics: input.generics,
         variants,
+        options,
     })
 }
 
+/// Pick the smallest unsigned (or with `signed`, signed) integer type that
+/// holds every discriminant, for `auto` and `auto_signed`. Every discriminant
+/// must be known at expansion time. Platform-dependent types are not considered.
+fn select_auto_repr(
+    auto: &Ident,
+    signed: bool,
+    variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+) -> Result<(IntType, Vec<EnumVariant>)> {
+    let candidates = IntType::ALL
+        .into_iter()
+        .filter(|ty| ty.is_signed() == signed && !matches!(ty, IntType::Usize | IntType::Isize));
+
+    for int_type in candidates {
+        if let Ok(calculated) = calculate_discriminants(variants, int_type) {
+            if calculated.iter().all(|v| v.value.is_some()) {
+                return Ok((int_type, calculated));
+            }
+        }
+    }
+
+    let all_known = |int_type| {
+        calculate_discriminants(variants, int_type)
+            .is_ok_and(|calculated| calculated.iter().all(|v| v.value.is_some()))
+    };
+    if !signed && all_known(IntType::I128) {
+        return Err(Error::new_spanned(
+            auto,
+            "`auto` picks an unsigned type, but some discriminants are negative; use `auto_signed`",
+        ));
+    }
+
+    // Report the variant that can't be evaluated or doesn't fit in the widest candidate
+    let widest = if signed { IntType::I128 } else { IntType::U128 };
+    let calculated = calculate_discriminants(variants, widest)?;
+    match calculated.iter().find(|v| v.value.is_none()) {
+        Some(variant) => Err(Error::new_spanned(
+            &variant.name,
+            format!(
+                "`{}` needs the value of every discriminant, but the discriminant of `{}` cannot be evaluated by the macro",
+                auto, variant.name
+            ),
+        )),
+        None => unreachable!("every discriminant fits in the widest type"),
+    }
+}
+
+/// Validate an `ascii` enum: the repr type must be `u8` and every known
+/// discriminant must be an ASCII character.
+fn validate_ascii(repr_type: &Ident, int_type: IntType, variants: &[EnumVariant]) -> Result<()> {
+    if int_type != IntType::U8 {
+        return Err(Error::new_spanned(
+            repr_type,
+            "the `ascii` option requires a `u8` repr type",
+        ));
+    }
+    for variant in variants {
+        if let Some(value) = variant.value.filter(|value| *value > 0x7F) {
+            return Err(Error::new_spanned(
+                &variant.name,
+                format!(
+                    "discriminant of `{}` is not an ASCII character: {:#04X}",
+                    variant.name, value
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Validate the `none` sentinel: it must fit in the repr type and must not
+/// collide with any known discriminant.
+fn validate_sentinel(sentinel: &Expr, int_type: IntType, variants: &[EnumVariant]) -> Result<()> {
+    let value = eval::evaluate(sentinel, int_type)
+        .map_err(|err| Error::new_spanned(sentinel, format!("invalid `none` sentinel: {}", err)))?;
+    let Some(value) = value else {
+        return Ok(());
+    };
+    match variants.iter().find(|v| v.value == Some(value)) {
+        Some(variant) => Err(Error::new_spanned(
+            sentinel,
+            format!(
+                "`none` sentinel {} collides with the discriminant of `{}`",
+                value, variant.name
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Validate a bit width given with `bits`: it must fit in both the repr type
+/// (excluding the sign bit) and the `u32` words values are packed into, and
+/// every known discriminant must fit in it.
+fn validate_bits(bits: &syn::LitInt, int_type: IntType, variants: &[EnumVariant]) -> Result<()> {
+    let width: u32 = bits.base10_parse()?;
+    let max_width = (int_type.bits() - u32::from(int_type.is_signed())).min(32);
+    if !(1..=max_width).contains(&width) {
+        return Err(Error::new_spanned(
+            bits,
+            format!(
+                "`bits` must be between 1 and {} for `{}`",
+                max_width,
+                int_type.name()
+            ),
+        ));
+    }
+    let max_value = (1i128 << width) - 1;
+    match variants
+        .iter()
+        .find(|v| v.value.is_some_and(|value| !(0..=max_value).contains(&value)))
+    {
+        Some(variant) => Err(Error::new_spanned(
+            &variant.name,
+            format!(
+                "discriminant of `{}` does not fit in {} bits",
+                variant.name, width
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Validate the `mask` option: every known discriminant must lie within the mask.
+fn validate_mask(mask: &Expr, int_type: IntType, variants: &[EnumVariant]) -> Result<()> {
+    let value = eval::evaluate(mask, int_type)
+        .map_err(|err| Error::new_spanned(mask, format!("invalid `mask`: {}", err)))?;
+    let Some(mask_value) = value else {
+        return Ok(());
+    };
+    match variants
+        .iter()
+        .find(|v| v.value.is_some_and(|value| value & !mask_value != 0))
+    {
+        Some(variant) => Err(Error::new_spanned(
+            &variant.name,
+            format!(
+                "discriminant of `{}` has bits outside of the `mask`",
+                variant.name
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Validate the `reserved` values and ranges: their bounds must fit in the repr
+/// type and no known discriminant may fall inside them.
+fn validate_reserved(reserved: &[Expr], int_type: IntType, variants: &[EnumVariant]) -> Result<()> {
+    let bound = |expr: Option<&Expr>, default: i128| -> Result<Option<i128>> {
+        match expr {
+            Some(expr) => eval::evaluate(expr, int_type)
+                .map_err(|err| Error::new_spanned(expr, format!("invalid `reserved` bound: {}", err))),
+            None => Ok(Some(default)),
+        }
+    };
+
+    for entry in reserved {
+        let (start, end) = match entry {
+            Expr::Range(range) => {
+                let start = bound(range.start.as_deref(), int_type.min_value())?;
+                let end = match range.limits {
+                    syn::RangeLimits::Closed(_) => bound(range.end.as_deref(), int_type.max_value())?,
+                    // An open range extends to the largest value
+                    syn::RangeLimits::HalfOpen(_) => match range.end.as_deref() {
+                        None => Some(int_type.max_value()),
+                        end => bound(end, 0)?.and_then(|end| end.checked_sub(1)),
+                    },
+                };
+                (start, end)
+            }
+            value => {
+                let value = bound(Some(value), 0)?;
+                (value, value)
+            }
+        };
+        let (Some(start), Some(end)) = (start, end) else {
+            continue;
+        };
+        if let Some(variant) = variants
+            .iter()
+            .find(|v| v.value.is_some_and(|value| (start..=end).contains(&value)))
+        {
+            return Err(Error::new_spanned(
+                &variant.name,
+                format!(
+                    "discriminant of `{}` falls in a reserved range",
+                    variant.name
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Validate a `flags` enum: every known discriminant must be a single bit of the
+/// repr type.
+fn validate_flags(int_type: IntType, variants: &[EnumVariant]) -> Result<()> {
+    let type_mask = u128::MAX >> (128 - int_type.bits());
+    match variants
+        .iter()
+        .find(|v| v.value.is_some_and(|value| (value as u128 & type_mask).count_ones() != 1))
+    {
+        Some(variant) => Err(Error::new_spanned(
+            &variant.name,
+            format!(
+                "discriminant of `{}` is not a single bit, which the `flags` option requires",
+                variant.name
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Validate the `other` variant: its name must not be taken by a variant, and
+/// it can't be combined with options that rely on a fixed set of values. The
+/// enum loses its discriminants, so implicit values may not depend on `cfg`.
+fn validate_other(other: &Ident, options: &ReprOptions, variants: &[EnumVariant]) -> Result<()> {
+    if variants.iter().any(|v| v.name == *other) {
+        return Err(Error::new_spanned(
+            other,
+            format!("`{}` is already a variant of the enum", other),
+        ));
+    }
+    let first_gated = variants.iter().position(|v| !v.cfg.is_empty());
+    if let Some(variant) = first_gated
+        .and_then(|first| variants[first + 1..].iter().find(|v| v.discriminant.is_none()))
+    {
+        return Err(Error::new_spanned(
+            &variant.name,
+            format!(
+                "`{}` follows a `cfg`-gated variant and needs an explicit discriminant with `other`",
+                variant.name
+            ),
+        ));
+    }
+    let conflicting = [
+        ("nonzero", options.nonzero),
+        ("bits", options.bits.is_some()),
+        ("reserved", !options.reserved.is_empty()),
+        ("flags", options.flags),
+        ("set", options.set),
+        ("map", options.map),
+        ("packed", options.packed),
+        ("open", options.open),
+        ("codec", options.codec),
+        ("compose", options.compose.is_some()),
+    ];
+    match conflicting.into_iter().find(|(_, enabled)| *enabled) {
+        Some((option, _)) => Err(Error::new_spanned(
+            other,
+            format!("`other` can't be combined with `{}`", option),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Validate a `codec` enum: the repr type must have the same width on every
+/// target, and the enum can't be generic, as the field types must be known to
+/// implement the generated wire traits.
+fn validate_codec(repr_type: &Ident, int_type: IntType, generics: &syn::Generics) -> Result<()> {
+    if matches!(int_type, IntType::Usize | IntType::Isize) {
+        return Err(Error::new_spanned(
+            repr_type,
+            format!(
+                "the `codec` option requires a fixed-width repr type, not `{}`",
+                repr_type
+            ),
+        ));
+    }
+    if !generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            generics,
+            "the `codec` option doesn't support generic enums",
+        ));
+    }
+    Ok(())
+}
+
+/// Validate the repr type of a `compose` enum, which is split in two halves:
+/// it must be given explicitly and have the same width on every target.
+fn validate_compose(repr_type: &Ident, int_type: Option<IntType>) -> Result<IntType> {
+    match int_type {
+        Some(IntType::Usize | IntType::Isize) => Err(Error::new_spanned(
+            repr_type,
+            format!(
+                "the `compose` option requires a fixed-width repr type, not `{}`",
+                repr_type
+            ),
+        )),
+        Some(int_type) => Ok(int_type),
+        None => Err(Error::new_spanned(
+            repr_type,
+            format!("the `compose` option can't be used with `{}`", repr_type),
+        )),
+    }
+}
+
+/// Rewrite a `(Hi, Lo)` discriminant of a `compose` enum into the composed
+/// value, with bare variant names resolved in the sub-enums, returning the two
+/// parts. Other expressions are left unchanged.
+fn compose_discriminant(
+    variant: &mut syn::Variant,
+    compose: &Compose,
+    int_type: IntType,
+) -> Result<Option<(Expr, Expr)>> {
+    let Some((_, Expr::Tuple(tuple))) = &variant.discriminant else {
+        return Ok(None);
+    };
+    if tuple.elems.len() != 2 {
+        return Err(Error::new_spanned(
+            tuple,
+            format!(
+                "discriminant of `{}` must be a `(high, low)` pair with `compose`",
+                variant.ident
+            ),
+        ));
+    }
+
+    let part = |expr: &Expr, ty: &syn::Path| -> Expr {
+        match expr {
+            Expr::Path(path) if path.qself.is_none() && path.path.get_ident().is_some() => {
+                let ident = path.path.get_ident();
+                syn::parse_quote! { #ty::#ident }
+            }
+            _ => expr.clone(),
+        }
+    };
+    let hi = part(&tuple.elems[0], &compose.hi);
+    let lo = part(&tuple.elems[1], &compose.lo);
+    let repr = Ident::new(int_type.name(), proc_macro2::Span::call_site());
+    let shift = proc_macro2::Literal::u32_unsuffixed(int_type.bits() / 2);
+    let expr: Expr = syn::parse_quote! {
+        ((#hi.as_repr() as #repr) << #shift) | ((#lo.as_repr() as #repr) & (((1 as #repr) << #shift) - 1))
+    };
+    if let Some((_, discriminant)) = &mut variant.discriminant {
+        *discriminant = expr;
+    }
+    Ok(Some((hi, lo)))
+}
+
+/// Validate a `nonzero` enum: no known discriminant may be zero.
+fn validate_nonzero(variants: &[EnumVariant]) -> Result<()> {
+    match variants.iter().find(|v| v.value == Some(0)) {
+        Some(variant) => Err(Error::new_spanned(
+            &variant.name,
+            format!(
+                "discriminant of `{}` is zero, which the `nonzero` option does not allow",
+                variant.name
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Validate the additional conversion types: each must be a primitive integer
+/// type other than the repr type, listed at most once.
+fn validate_also(int_type: IntType, also: &[Ident]) -> Result<()> {
+    for (i, ty) in also.iter().enumerate() {
+        match IntType::from_ident(ty) {
+            None => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "`also` expects primitive integer types",
+                ))
+            }
+            Some(also_type) if also_type == int_type => {
+                return Err(Error::new_spanned(
+                    ty,
+                    format!("`{}` is already the repr type", ty),
+                ))
+            }
+            Some(_) if also[..i].contains(ty) => {
+                return Err(Error::new_spanned(
+                    ty,
+                    format!("`{}` is listed more than once", ty),
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Render a path for use in error messages.
+pub(crate) fn path_to_string(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Parse the `compose(hi = Category, lo = Code)` option.
+fn parse_compose(meta: &ParseNestedMeta) -> Result<Compose> {
+    let mut hi: Option<syn::Path> = None;
+    let mut lo: Option<syn::Path> = None;
+    meta.parse_nested_meta(|inner| {
+        if inner.path.is_ident("hi") {
+            hi = Some(inner.value()?.parse()?);
+            Ok(())
+        } else if inner.path.is_ident("lo") {
+            lo = Some(inner.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(inner.error(format!(
+                "unknown compose option `{}`, expected `hi` or `lo`",
+                path_to_string(&inner.path)
+            )))
+        }
+    })?;
+    let (Some(hi), Some(lo)) = (hi, lo) else {
+        return Err(meta.error("`compose` requires both `hi = Enum` and `lo = Enum`"));
+    };
+    // The accessors are named after the sub-enums
+    if hi.segments.last().map(|s| &s.ident) == lo.segments.last().map(|s| &s.ident) {
+        return Err(meta.error("`compose` requires `hi` and `lo` enums of different names"));
+    }
+    Ok(Compose { hi, lo, parts: vec![] })
+}
+
+/// Parse the `newtype` option, either `newtype = Opcode` for a tuple struct with
+/// an accessible field, or `newtype(Opcode, new = new, get = get)` to go through
+/// user-declared functions instead.
+fn parse_newtype(meta: &ParseNestedMeta) -> Result<Newtype> {
+    if meta.input.peek(Token![=]) {
+        return Ok(Newtype {
+            ty: meta.value()?.parse()?,
+            new: None,
+            get: None,
+        });
+    }
+
+    let content;
+    syn::parenthesized!(content in meta.input);
+    let mut newtype = Newtype {
+        ty: content.parse()?,
+        new: None,
+        get: None,
+    };
+    while !content.is_empty() {
+        content.parse::<Token![,]>()?;
+        if content.is_empty() {
+            break;
+        }
+        let key: Ident = content.parse()?;
+        content.parse::<Token![=]>()?;
+        let value: Ident = content.parse()?;
+        if key == "new" {
+            newtype.new = Some(value);
+        } else if key == "get" {
+            newtype.get = Some(value);
+        } else {
+            return Err(Error::new_spanned(
+                &key,
+                format!("unknown newtype option `{}`, expected `new` or `get`", key),
+            ));
+        }
+    }
+    Ok(newtype)
+}
+
 /// Parse the repr type from existing #[repr(...)] attributes if no args provided.
 pub fn extract_repr_from_attrs(input: &DeriveInput) -> Result<Option<Ident>> {
     for attr in &input.attrs {
@@ -88,34 +814,93 @@ fn is_matching_repr_attr(attr: &syn::Attribute, repr_type: &Ident) -> bool {
 }
 
 /// Calculate discriminants for all variants, handling both explicit and implicit values.
+///
+/// Implicit variants that follow an explicit expression which cannot be
+/// evaluated here are tracked relative to that expression. Values that are
+/// known to overflow `int_type` are reported on the offending variant.
 fn calculate_discriminants(
     variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    int_type: IntType,
 ) -> Result<Vec<EnumVariant>> {
     let mut result = Vec::new();
-    let mut next_implicit_value: i128 = 0;
+    // The most recent explicit expression that could not be evaluated, if any,
+    // and the offset of the next implicit variant relative to it (or to zero).
+    let mut implicit_base: Option<syn::Expr> = None;
+    let mut next_implicit_offset: i128 = 0;
+    // Set after a `cfg`-gated variant, whose presence shifts the implicit
+    // discriminants that follow it
+    let mut after_cfg_variant = false;
 
     for variant in variants.iter() {
-        let calculated_discriminant = if let Some((_, expr)) = &variant.discriminant {
+        let cfg = cfg_attributes(&variant.attrs)?;
+
+        let discriminant = variant
+            .discriminant
+            .as_ref()
+            .map(|(_, expr)| char_discriminant_to_int(variant, expr, int_type))
+            .transpose()?;
+
+        let (value, calculated_discriminant) = if let Some(expr) = &discriminant {
             // Explicit discriminant - try to evaluate it for the next implicit value
-            if let Ok(value) = try_evaluate_expr(expr) {
-                next_implicit_value = value + 1;
+            let value = eval::evaluate(expr, int_type).map_err(|err| {
+                Error::new_spanned(
+                    variant,
+                    format!("invalid discriminant for `{}`: {}", variant.ident, err),
+                )
+            })?;
+            if let Some(value) = value {
+                after_cfg_variant = false;
+                (implicit_base, next_implicit_offset) = next_implicit(value);
             } else {
-                // Can't evaluate - reset implicit counter
+                // Can't evaluate - track later implicit values relative to it.
                 // This handles complex expressions like consts
-                next_implicit_value = 0;
+                implicit_base = Some(expr.clone());
+                next_implicit_offset = 1;
             }
-            CalculatedDiscriminant::Explicit(expr.clone())
+            (value, CalculatedDiscriminant::Explicit(expr.clone()))
         } else {
             // Implicit discriminant
-            let value = next_implicit_value;
-            next_implicit_value += 1;
-            CalculatedDiscriminant::Implicit(value)
+            let offset = next_implicit_offset;
+            if implicit_base.is_none() && !after_cfg_variant && !int_type.contains(offset) {
+                return Err(Error::new_spanned(
+                    variant,
+                    format!(
+                        "discriminant of `{}` overflows `{}`",
+                        variant.ident,
+                        int_type.name()
+                    ),
+                ));
+            }
+            match implicit_base.clone() {
+                Some(base) => {
+                    next_implicit_offset += 1;
+                    (None, CalculatedDiscriminant::Relative { base, offset })
+                }
+                None => {
+                    (implicit_base, next_implicit_offset) = next_implicit(offset);
+                    (Some(offset), CalculatedDiscriminant::Implicit(offset))
+                }
+            }
+        };
+
+        // The values of gated variants and of the implicit ones after them
+        // depend on the enabled features and are checked by the compiler
+        let value = if (after_cfg_variant && discriminant.is_none()) || !cfg.is_empty() {
+            None
+        } else {
+            value
         };
+        after_cfg_variant |= !cfg.is_empty();
 
+        let (attributes, options) = parse_variant_options(variant, int_type)?;
         result.push(EnumVariant {
             name: variant.ident.clone(),
-            attributes: variant.attrs.clone(),
-            discriminant: variant.discriminant.as_ref().map(|(_, expr)| expr.clone()),
+            fields: variant.fields.clone(),
+            attributes,
+            cfg,
+            discriminant,
+            value,
+            options,
             calculated_discriminant,
         });
     }
@@ -123,28 +908,249 @@ fn calculate_discriminants(
     Ok(result)
 }
 
-/// Try to evaluate a simple expression to an i128 value.
-/// This is best-effort and only handles simple literal cases.
-fn try_evaluate_expr(expr: &syn::Expr) -> Result<i128> {
-    match expr {
-        syn::Expr::Lit(lit_expr) => match &lit_expr.lit {
-            syn::Lit::Int(lit_int) => lit_int
-                .base10_parse::<i128>()
-                .map_err(|e| Error::new_spanned(lit_int, e)),
-            _ => Err(Error::new_spanned(expr, "expected integer literal")),
-        },
-        syn::Expr::Unary(unary) => match &unary.op {
-            syn::UnOp::Neg(_) => {
-                let value = try_evaluate_expr(&unary.expr)?;
-                Ok(-value)
-            }
-            _ => Err(Error::new_spanned(expr, "only negation is supported")),
-        },
-        _ => Err(Error::new_spanned(
-            expr,
-            "cannot evaluate complex expression",
-        )),
+/// The base and offset of the implicit discriminant following `value`. Past
+/// `i128::MAX`, which only `u128` can hold, values aren't tracked: the next
+/// discriminant is taken relative to `value`, and an implicit variant following
+/// the largest value of the repr type is reported by the compiler.
+fn next_implicit(value: i128) -> (Option<Expr>, i128) {
+    match value.checked_add(1) {
+        Some(next) => (None, next),
+        None => {
+            let lit = proc_macro2::Literal::i128_unsuffixed(value);
+            (Some(syn::parse_quote! { #lit }), 1)
+        }
+    }
+}
+
+/// Collect the attributes that control whether a variant exists: `cfg`
+/// attributes as they are, and `cfg_attr` attributes reduced to the `cfg`
+/// attributes they expand to. Other attributes of `cfg_attr` are dropped, as
+/// they may not be valid on the generated code.
+fn cfg_attributes(attrs: &[Attribute]) -> Result<Vec<Attribute>> {
+    let mut result = Vec::new();
+    for attr in attrs {
+        if attr.path().is_ident("cfg") {
+            result.push(attr.clone());
+        } else if attr.path().is_ident("cfg_attr") {
+            if let Some(meta) = reduce_cfg_attr(&attr.meta)? {
+                result.push(syn::parse_quote! { #[#meta] });
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Reduce `cfg_attr(predicate, attrs...)` to the `cfg` and nested `cfg_attr`
+/// attributes in `attrs`, or `None` if there are none.
+fn reduce_cfg_attr(meta: &Meta) -> Result<Option<Meta>> {
+    let list = meta.require_list()?;
+    let mut args = list
+        .parse_args_with(syn::punctuated::Punctuated::<Meta, Token![,]>::parse_terminated)?
+        .into_iter();
+    let Some(predicate) = args.next() else {
+        return Ok(None);
+    };
+
+    let mut kept = Vec::new();
+    for arg in args {
+        if arg.path().is_ident("cfg") {
+            kept.push(arg);
+        } else if arg.path().is_ident("cfg_attr") {
+            kept.extend(reduce_cfg_attr(&arg)?);
+        }
+    }
+    if kept.is_empty() {
+        return Ok(None);
     }
+    Ok(Some(syn::parse_quote! { cfg_attr(#predicate, #(#kept),*) }))
+}
+
+/// Split the `#[repr_cast(...)]` attributes of a variant from the attributes
+/// that are passed through, and parse them into `VariantOptions`.
+fn parse_variant_options(
+    variant: &syn::Variant,
+    int_type: IntType,
+) -> Result<(Vec<Attribute>, VariantOptions)> {
+    let mut attributes = Vec::new();
+    let mut options = VariantOptions::default();
+
+    for attr in &variant.attrs {
+        if !attr.path().is_ident("repr_cast") {
+            attributes.push(attr.clone());
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("alias") {
+                let expr: Expr = meta.value()?.parse()?;
+                let expr = char_discriminant_to_int(variant, &expr, int_type)?;
+                options
+                    .accepts
+                    .push(accepted_alias(&variant.ident, expr, int_type)?);
+                Ok(())
+            } else if meta.path.is_ident("range") {
+                let expr: Expr = meta.value()?.parse()?;
+                let Expr::Range(range) = expr else {
+                    return Err(Error::new_spanned(
+                        expr,
+                        "expected a range such as `200..=299`",
+                    ));
+                };
+                options
+                    .accepts
+                    .push(accepted_range(&variant.ident, range, int_type)?);
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+                Ok(())
+            } else if meta.path.is_ident("rename") {
+                options.rename = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("hidden") {
+                options.hidden = true;
+                Ok(())
+            } else {
+                Err(meta.error(format!(
+                    "unknown repr_cast variant option `{}`",
+                    path_to_string(&meta.path)
+                )))
+            }
+        })?;
+    }
+
+    if options.skip && !options.accepts.is_empty() {
+        return Err(Error::new_spanned(
+            &variant.ident,
+            format!(
+                "`{}` is skipped by `from_repr` and cannot have aliases or ranges",
+                variant.ident
+            ),
+        ));
+    }
+
+    Ok((attributes, options))
+}
+
+/// Build the values accepted for `alias = expr`.
+fn accepted_alias(variant: &Ident, expr: Expr, int_type: IntType) -> Result<AcceptedValues> {
+    let value = eval::evaluate(&expr, int_type).map_err(|err| {
+        Error::new_spanned(&expr, format!("invalid alias for `{}`: {}", variant, err))
+    })?;
+    Ok(AcceptedValues {
+        source: expr.clone(),
+        start: Some(expr.clone()),
+        end: Some(expr),
+        bounds: value.map(|value| (value, value)),
+    })
+}
+
+/// Build the values accepted for `range = start..=end`. Open bounds extend to
+/// the limits of the repr type; bounds known to be at those limits are dropped
+/// so that no always-true comparison is generated.
+fn accepted_range(
+    variant: &Ident,
+    range: syn::ExprRange,
+    int_type: IntType,
+) -> Result<AcceptedValues> {
+    let evaluate = |expr: &Expr| {
+        eval::evaluate(expr, int_type).map_err(|err| {
+            Error::new_spanned(expr, format!("invalid range for `{}`: {}", variant, err))
+        })
+    };
+
+    let (start, start_value) = match range.start.as_deref() {
+        Some(start) => (Some(start.clone()), evaluate(start)?),
+        None => (None, Some(int_type.min_value())),
+    };
+    let (end, end_value) = match (range.end.as_deref(), &range.limits) {
+        (Some(end), syn::RangeLimits::Closed(_)) => (Some(end.clone()), evaluate(end)?),
+        (Some(end), syn::RangeLimits::HalfOpen(_)) => (
+            Some(syn::parse_quote! { (#end) - 1 }),
+            evaluate(end)?.map(|end| end - 1),
+        ),
+        (None, _) => (None, Some(int_type.max_value())),
+    };
+    let start = start.filter(|_| start_value != Some(int_type.min_value()));
+    let end = end.filter(|_| end_value != Some(int_type.max_value()));
+
+    let bounds = start_value.zip(end_value);
+    if bounds.is_some_and(|(start, end)| start > end) {
+        return Err(Error::new_spanned(
+            &range,
+            format!("range of `{}` is empty", variant),
+        ));
+    }
+
+    Ok(AcceptedValues {
+        source: Expr::Range(range),
+        start,
+        end,
+        bounds,
+    })
+}
+
+/// Validate that the aliases and ranges of each variant overlap neither the
+/// discriminants nor the aliases and ranges of other variants.
+fn validate_accepted_values(variants: &[EnumVariant]) -> Result<()> {
+    let discriminants = variants.iter().filter_map(|v| {
+        v.value.map(|value| {
+            (&v.name, (value, value), format!("the discriminant of `{}`", v.name))
+        })
+    });
+    let mut seen: Vec<(&Ident, (i128, i128), String)> = discriminants.collect();
+
+    for variant in variants {
+        for accepted in &variant.options.accepts {
+            let Some((start, end)) = accepted.bounds else {
+                continue;
+            };
+            if let Some((_, _, other)) = seen.iter().find(|(name, (other_start, other_end), _)| {
+                *name != &variant.name && start <= *other_end && *other_start <= end
+            }) {
+                return Err(Error::new_spanned(
+                    &accepted.source,
+                    format!("{} overlaps {}", accepted.describe(&variant.name), other),
+                ));
+            }
+            seen.push((&variant.name, (start, end), accepted.describe(&variant.name)));
+        }
+    }
+    Ok(())
+}
+
+/// Rewrite a char literal discriminant (`Get = 'G'`) into an integer literal
+/// of the repr type, which is what Rust accepts as a discriminant. A byte
+/// literal is used for `u8` enums. Other expressions are returned unchanged.
+fn char_discriminant_to_int(variant: &syn::Variant, expr: &Expr, int_type: IntType) -> Result<Expr> {
+    let Expr::Lit(syn::ExprLit {
+        lit: Lit::Char(lit_char),
+        ..
+    }) = expr
+    else {
+        return Ok(expr.clone());
+    };
+
+    let code = u32::from(lit_char.value());
+    if !int_type.contains(i128::from(code)) {
+        return Err(Error::new_spanned(
+            variant,
+            format!(
+                "character {:?} of `{}` does not fit in `{}`",
+                lit_char.value(),
+                variant.ident,
+                int_type.name()
+            ),
+        ));
+    }
+
+    let mut lit = match int_type {
+        IntType::U8 => proc_macro2::Literal::byte_character(code as u8),
+        _ => proc_macro2::Literal::u32_unsuffixed(code),
+    };
+    lit.set_span(lit_char.span());
+    Ok(Expr::Lit(syn::ExprLit {
+        attrs: Vec::new(),
+        lit: Lit::new(lit),
+    }))
 }
 
 #[cfg(test)]
@@ -152,6 +1158,13 @@ mod tests {
     use super::*;
     use syn::parse_quote;
 
+    /// Parses attribute arguments the way the `repr_cast` entry point does.
+    fn parse_args(tokens: proc_macro2::TokenStream) -> Result<ReprCastArgs> {
+        let mut args = ReprCastArgs::default();
+        syn::parse::Parser::parse2(syn::meta::parser(|meta| args.parse(meta)), tokens)?;
+        Ok(args)
+    }
+
     #[test]
     fn test_parse_simple_enum() {
         let input: DeriveInput = parse_quote! {
@@ -163,7 +1176,7 @@ mod tests {
         };
 
         let repr_type: Ident = parse_quote! { u8 };
-        let result = parse_repr_cast(repr_type, input);
+        let result = parse_repr_cast(repr_type, Default::default(), input);
 
         assert!(result.is_ok());
         let repr_enum = result.unwrap();
@@ -183,7 +1196,7 @@ mod tests {
         };
 
         let repr_type: Ident = parse_quote! { u8 };
-        let result = parse_repr_cast(repr_type, input);
+        let result = parse_repr_cast(repr_type, Default::default(), input);
 
         assert!(result.is_ok());
         let repr_enum = result.unwrap();
@@ -215,7 +1228,7 @@ mod tests {
         };
 
         let repr_type: Ident = parse_quote! { i32 };
-        let result = parse_repr_cast(repr_type, input);
+        let result = parse_repr_cast(repr_type, Default::default(), input);
 
         assert!(result.is_ok());
         let repr_enum = result.unwrap();
@@ -234,7 +1247,7 @@ mod tests {
         };
 
         let repr_type: Ident = parse_quote! { u16 };
-        let result = parse_repr_cast(repr_type, input);
+        let result = parse_repr_cast(repr_type, Default::default(), input);
 
         assert!(result.is_ok());
         let repr_enum = result.unwrap();
@@ -256,85 +1269,1061 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_rejects_non_enum() {
+    fn test_parse_implicit_after_const_expression() {
         let input: DeriveInput = parse_quote! {
-            struct NotAnEnum {
-                field: u32,
+            enum WithConsts {
+                First,
+                Second = BASE + 1,
+                Third,
+                Fourth,
+                Fifth = 20,
+                Sixth,
             }
         };
 
         let repr_type: Ident = parse_quote! { u8 };
-        let result = parse_repr_cast(repr_type, input);
+        let repr_enum = parse_repr_cast(repr_type, Default::default(), input).unwrap();
 
-        assert!(result.is_err());
-        let err_msg = result.unwrap_err().to_string();
-        assert!(err_msg.contains("can only be applied to enums"));
+        // Third and Fourth are tracked relative to `BASE + 1`
+        match &repr_enum.variants[2].calculated_discriminant {
+            CalculatedDiscriminant::Relative { base, offset } => {
+                assert_eq!(quote::quote!(#base).to_string(), "BASE + 1");
+                assert_eq!(*offset, 1);
+            }
+            _ => panic!("Expected relative discriminant"),
+        }
+        match &repr_enum.variants[3].calculated_discriminant {
+            CalculatedDiscriminant::Relative { offset, .. } => assert_eq!(*offset, 2),
+            _ => panic!("Expected relative discriminant"),
+        }
+        // A literal discriminant makes later values known again
+        match &repr_enum.variants[5].calculated_discriminant {
+            CalculatedDiscriminant::Implicit(v) => assert_eq!(*v, 21),
+            _ => panic!("Expected implicit discriminant"),
+        }
     }
 
     #[test]
-    fn test_parse_rejects_enum_with_fields() {
+    fn test_parse_largest_discriminants() {
         let input: DeriveInput = parse_quote! {
-            enum WithFields {
-                Variant1(u32),
-                Variant2,
+            enum Largest {
+                Max = i128::MAX,
             }
         };
+        let repr_type: Ident = parse_quote! { i128 };
+        let repr_enum = parse_repr_cast(repr_type, Default::default(), input).unwrap();
+        assert_eq!(repr_enum.variants[0].value, Some(i128::MAX));
+
+        // Values past `i128::MAX` aren't tracked, so the variant after it is
+        // left to the compiler
+        let input: DeriveInput = parse_quote! {
+            enum Largest {
+                Max = 170141183460469231731687303715884105727,
+                AfterMax,
+            }
+        };
+        let repr_type: Ident = parse_quote! { u128 };
+        let repr_enum = parse_repr_cast(repr_type, Default::default(), input).unwrap();
+        assert_eq!(repr_enum.variants[1].value, None);
+        match &repr_enum.variants[1].calculated_discriminant {
+            CalculatedDiscriminant::Relative { base, offset } => {
+                assert_eq!(quote::quote!(#base).to_string(), "170141183460469231731687303715884105727");
+                assert_eq!(*offset, 1);
+            }
+            _ => panic!("Expected relative discriminant"),
+        }
+    }
 
-        let repr_type: Ident = parse_quote! { u8 };
-        let result = parse_repr_cast(repr_type, input);
-
-        assert!(result.is_err());
-        let err_msg = result.unwrap_err().to_string();
-        assert!(err_msg.contains("fieldless enums"));
-    }
-
     #[test]
-    fn test_extract_repr_from_attrs() {
+    fn test_parse_evaluates_const_folded_discriminants() {
         let input: DeriveInput = parse_quote! {
-            #[repr(u32)]
-            enum Test {
-                A,
+            enum Folded {
+                Shifted = 1 << 4,
+                Next,
+                Ored = 0x20 | 0x01,
+                AfterOr,
+                Max = u8::MAX - 1,
+                Last,
             }
         };
 
-        let result = extract_repr_from_attrs(&input).unwrap();
-        assert!(result.is_some());
-        assert_eq!(result.unwrap().to_string(), "u32");
+        let repr_type: Ident = parse_quote! { u8 };
+        let repr_enum = parse_repr_cast(repr_type, Default::default(), input).unwrap();
+
+        match &repr_enum.variants[1].calculated_discriminant {
+            CalculatedDiscriminant::Implicit(v) => assert_eq!(*v, 17),
+            _ => panic!("Expected implicit discriminant"),
+        }
+        match &repr_enum.variants[3].calculated_discriminant {
+            CalculatedDiscriminant::Implicit(v) => assert_eq!(*v, 34),
+            _ => panic!("Expected implicit discriminant"),
+        }
+        match &repr_enum.variants[5].calculated_discriminant {
+            CalculatedDiscriminant::Implicit(v) => assert_eq!(*v, 255),
+            _ => panic!("Expected implicit discriminant"),
+        }
     }
 
     #[test]
-    fn test_extract_repr_none() {
+    fn test_parse_rejects_overflowing_discriminant() {
+        let input: DeriveInput = parse_quote! {
+            enum Overflowing {
+                Big = 200 + 100,
+            }
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, Default::default(), input).unwrap_err();
+        assert!(err.to_string().contains("invalid discriminant for `Big`"));
+        assert!(err.to_string().contains("overflow"));
+    }
+
+    #[test]
+    fn test_parse_rejects_overflowing_implicit_discriminant() {
+        let input: DeriveInput = parse_quote! {
+            enum Overflowing {
+                Last = 255,
+                Beyond,
+            }
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, Default::default(), input).unwrap_err();
+        assert!(err.to_string().contains("discriminant of `Beyond` overflows `u8`"));
+    }
+
+    #[test]
+    fn test_parse_rejects_non_integer_repr() {
         let input: DeriveInput = parse_quote! {
             enum Test {
                 A,
             }
         };
 
-        let result = extract_repr_from_attrs(&input).unwrap();
-        assert!(result.is_none());
+        let repr_type: Ident = parse_quote! { C };
+        let err = parse_repr_cast(repr_type, Default::default(), input).unwrap_err();
+        assert!(err.to_string().contains("primitive integer repr type"));
     }
 
     #[test]
-    fn test_try_evaluate_expr_positive() {
-        let expr: syn::Expr = parse_quote! { 42 };
-        let result = try_evaluate_expr(&expr);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 42);
+    fn test_parse_char_discriminants() {
+        let input: DeriveInput = parse_quote! {
+            enum Command {
+                Get = 'G',
+                Next,
+                Put = b'P',
+            }
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let repr_enum = parse_repr_cast(repr_type, Default::default(), input).unwrap();
+
+        // Char literals are rewritten to byte literals for u8 enums
+        let discriminant = repr_enum.variants[0].discriminant.as_ref().unwrap();
+        assert_eq!(quote::quote!(#discriminant).to_string(), "b'G'");
+        assert_eq!(repr_enum.variants[0].value, Some(71));
+        assert_eq!(repr_enum.variants[1].value, Some(72));
+        assert_eq!(repr_enum.variants[2].value, Some(80));
     }
 
     #[test]
-    fn test_try_evaluate_expr_negative() {
-        let expr: syn::Expr = parse_quote! { -10 };
-        let result = try_evaluate_expr(&expr);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), -10);
+    fn test_parse_char_discriminant_wider_repr() {
+        let input: DeriveInput = parse_quote! {
+            enum Symbol {
+                Lambda = 'λ',
+            }
+        };
+
+        let repr_type: Ident = parse_quote! { u16 };
+        let repr_enum = parse_repr_cast(repr_type, Default::default(), input).unwrap();
+        assert_eq!(repr_enum.variants[0].value, Some(0x3BB));
+
+        let input: DeriveInput = parse_quote! {
+            enum Symbol {
+                Lambda = 'λ',
+            }
+        };
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, Default::default(), input).unwrap_err();
+        assert!(err.to_string().contains("does not fit in `u8`"));
+    }
+
+    #[test]
+    fn test_parse_ascii_option() {
+        let input: DeriveInput = parse_quote! {
+            enum Command {
+                Get = 'G',
+                Del = 0x7F,
+            }
+        };
+        let options = ReprOptions {
+            ascii: true,
+            ..Default::default()
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let repr_enum = parse_repr_cast(repr_type, options, input).unwrap();
+        assert!(repr_enum.options.ascii);
+    }
+
+    #[test]
+    fn test_parse_ascii_rejects_non_ascii() {
+        let input: DeriveInput = parse_quote! {
+            enum Command {
+                Get = 'G',
+                High = 0x80,
+            }
+        };
+        let options = ReprOptions {
+            ascii: true,
+            ..Default::default()
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, options.clone(), input.clone()).unwrap_err();
+        assert!(err.to_string().contains("`High` is not an ASCII character"));
+
+        let repr_type: Ident = parse_quote! { u16 };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("requires a `u8` repr type"));
+    }
+
+    #[test]
+    fn test_parse_detects_nonzero() {
+        let input: DeriveInput = parse_quote! {
+            enum Presence {
+                Present = 1,
+                Deferred,
+            }
+        };
+        let repr_type: Ident = parse_quote! { u8 };
+        let repr_enum = parse_repr_cast(repr_type, Default::default(), input).unwrap();
+        assert!(repr_enum.options.nonzero);
+
+        // Not detected with a zero or unknown discriminant
+        for input in [
+            parse_quote! { enum Presence { Absent, Present } },
+            parse_quote! { enum Presence { Present = BASE } },
+        ] {
+            let repr_type: Ident = parse_quote! { u8 };
+            let repr_enum = parse_repr_cast(repr_type, Default::default(), input).unwrap();
+            assert!(!repr_enum.options.nonzero);
+        }
+
+        // Nor with `nonzero = false`
+        let input: DeriveInput = parse_quote! {
+            enum Presence {
+                Present = 1,
+            }
+        };
+        let args = parse_args(quote::quote! { u8, nonzero = false }).unwrap();
+        assert!(args.options.nonzero_disabled);
+        let repr_enum = parse_repr_cast(args.repr_type.unwrap(), args.options, input).unwrap();
+        assert!(!repr_enum.options.nonzero);
+    }
+
+    #[test]
+    fn test_parse_nonzero_rejects_zero() {
+        let input: DeriveInput = parse_quote! {
+            enum Presence {
+                Absent = 0,
+                Present,
+            }
+        };
+        let options = ReprOptions {
+            nonzero: true,
+            ..Default::default()
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("discriminant of `Absent` is zero"));
+    }
+
+    #[test]
+    fn test_parse_none_sentinel() {
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                Pending,
+                Active,
+            }
+        };
+        let options = ReprOptions {
+            none: Some(parse_quote! { 0xFF }),
+            ..Default::default()
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let repr_enum = parse_repr_cast(repr_type, options, input).unwrap();
+        assert!(repr_enum.options.none.is_some());
+    }
+
+    #[test]
+    fn test_parse_none_sentinel_rejects_collision_and_overflow() {
+        for (sentinel, message) in [
+            (parse_quote! { 1 }, "collides with the discriminant of `Active`"),
+            (parse_quote! { 0x100 }, "invalid `none` sentinel"),
+        ] {
+            let input: DeriveInput = parse_quote! {
+                enum Status {
+                    Pending,
+                    Active,
+                }
+            };
+            let options = ReprOptions {
+                none: Some(sentinel),
+                ..Default::default()
+            };
+
+            let repr_type: Ident = parse_quote! { u8 };
+            let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_parse_bits() {
+        let input: DeriveInput = parse_quote! {
+            enum Mode {
+                Idle,
+                Run,
+                Sleep = 7,
+            }
+        };
+        let options = ReprOptions {
+            bits: Some(parse_quote! { 3 }),
+            ..Default::default()
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        assert!(parse_repr_cast(repr_type, options, input).is_ok());
+    }
+
+    #[test]
+    fn test_parse_bits_rejects_invalid_widths() {
+        for (repr_type, bits, message) in [
+            (quote::quote! { u8 }, quote::quote! { 2 }, "`Sleep` does not fit in 2 bits"),
+            (quote::quote! { u8 }, quote::quote! { 9 }, "between 1 and 8 for `u8`"),
+            (quote::quote! { i8 }, quote::quote! { 8 }, "between 1 and 7 for `i8`"),
+            (quote::quote! { u64 }, quote::quote! { 33 }, "between 1 and 32 for `u64`"),
+            (quote::quote! { u8 }, quote::quote! { 0 }, "between 1 and 8"),
+        ] {
+            let input: DeriveInput = parse_quote! {
+                enum Mode {
+                    Idle,
+                    Run,
+                    Sleep = 7,
+                }
+            };
+            let options = ReprOptions {
+                bits: Some(syn::parse2(bits).unwrap()),
+                ..Default::default()
+            };
+
+            let err = parse_repr_cast(syn::parse2(repr_type).unwrap(), options, input).unwrap_err();
+            assert!(err.to_string().contains(message), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_parse_mask_rejects_discriminant_outside_mask() {
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                Ready = 0x01,
+                Busy = 0x12,
+            }
+        };
+        let options = ReprOptions {
+            mask: Some(parse_quote! { 0x0F }),
+            ..Default::default()
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("`Busy` has bits outside of the `mask`"));
+    }
+
+    #[test]
+    fn test_parse_reserved_rejects_discriminant_in_range() {
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                Ready = 0x01,
+                Future = 0x12,
+            }
+        };
+        let options = ReprOptions {
+            reserved: vec![parse_quote! { 0xFF }, parse_quote! { 0x10..0x20 }],
+            ..Default::default()
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("`Future` falls in a reserved range"));
+    }
+
+    #[test]
+    fn test_parse_reserved_open_ranges() {
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                Ready = 0x01,
+                Future = 0x12,
+            }
+        };
+        let options = ReprOptions {
+            reserved: vec![parse_quote! { 0x10.. }],
+            ..Default::default()
+        };
+
+        for repr_type in ["u8", "u128", "i128"] {
+            let repr_type = Ident::new(repr_type, proc_macro2::Span::call_site());
+            let err = parse_repr_cast(repr_type, options.clone(), input.clone()).unwrap_err();
+            assert!(err.to_string().contains("`Future` falls in a reserved range"));
+        }
+
+        let options = ReprOptions {
+            reserved: vec![parse_quote! { 0x20.. }, parse_quote! { ..i128::MIN }],
+            ..Default::default()
+        };
+        let repr_type: Ident = parse_quote! { i128 };
+        assert!(parse_repr_cast(repr_type, options, input).is_ok());
+    }
+
+    #[test]
+    fn test_parse_flags_rejects_multiple_bits() {
+        let input: DeriveInput = parse_quote! {
+            enum Permission {
+                Read = 1,
+                Write = 2,
+                ReadWrite = 3,
+            }
+        };
+        let options = ReprOptions {
+            flags: true,
+            ..Default::default()
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("discriminant of `ReadWrite` is not a single bit"));
+    }
+
+    #[test]
+    fn test_parse_flags_sign_bit() {
+        let input: DeriveInput = parse_quote! {
+            enum Permission {
+                Low = 1,
+                High = -128,
+            }
+        };
+        let options = ReprOptions {
+            flags: true,
+            ..Default::default()
+        };
+
+        let repr_type: Ident = parse_quote! { i8 };
+        assert!(parse_repr_cast(repr_type, options, input).is_ok());
+    }
+
+    #[test]
+    fn test_parse_set_rejects_too_many_variants() {
+        let variants = (0..129u32).map(|i| quote::format_ident!("V{}", i));
+        let input: DeriveInput = parse_quote! {
+            enum Wide {
+                #(#variants),*
+            }
+        };
+        let options = ReprOptions {
+            set: true,
+            ..Default::default()
+        };
+
+        let repr_type: Ident = parse_quote! { u16 };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("at most 128 variants"));
+    }
+
+    #[test]
+    fn test_parse_atomic_rejects_128_bits() {
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                Pending,
+                Active,
+            }
+        };
+        let options = ReprOptions {
+            atomic: true,
+            ..Default::default()
+        };
+
+        let repr_type: Ident = parse_quote! { u128 };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("`atomic` option is not supported for `u128`"));
+    }
+
+    #[test]
+    fn test_parse_other() {
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                Pending = 1,
+                Active = 2,
+            }
+        };
+        let args = parse_args(quote::quote! { u8, other = Unknown }).unwrap();
+
+        let repr_enum = parse_repr_cast(args.repr_type.unwrap(), args.options, input).unwrap();
+        assert_eq!(repr_enum.options.other.unwrap(), "Unknown");
+        // `Unknown(0)` rules out NonZero conversions
+        assert!(!repr_enum.options.nonzero);
+    }
+
+    #[test]
+    fn test_parse_other_rejects_conflicts() {
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                Pending,
+                Unknown,
+            }
+        };
+        let options = ReprOptions {
+            other: Some(parse_quote! { Unknown }),
+            ..Default::default()
+        };
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("`Unknown` is already a variant"));
+
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                Pending,
+            }
+        };
+        let options = ReprOptions {
+            other: Some(parse_quote! { Unknown }),
+            set: true,
+            ..Default::default()
+        };
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("`other` can't be combined with `set`"));
+
+        // Unknown values, reserved or not, go to the `other` variant
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                Pending,
+            }
+        };
+        let options = ReprOptions {
+            other: Some(parse_quote! { Unknown }),
+            reserved: vec![parse_quote! { 0x10..=0x1F }],
+            ..Default::default()
+        };
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("`other` can't be combined with `reserved`"));
+
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                #[cfg(feature = "x")]
+                Pending,
+                Active,
+            }
+        };
+        let options = ReprOptions {
+            other: Some(parse_quote! { Unknown }),
+            ..Default::default()
+        };
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("`Active` follows a `cfg`-gated variant"));
+    }
+
+    #[test]
+    fn test_parse_args_reserved() {
+        let args = parse_args(quote::quote! { u8, reserved(0x10..=0x1F, 0xFF) }).unwrap();
+        assert_eq!(args.options.reserved.len(), 2);
+        assert!(matches!(args.options.reserved[0], Expr::Range(_)));
+    }
+
+    #[test]
+    fn test_parse_variant_aliases_and_ranges() {
+        let input: DeriveInput = parse_quote! {
+            enum Reply {
+                #[repr_cast(alias = 0)]
+                #[doc = "Accepted"]
+                Ok = 1,
+                #[repr_cast(range = 200..300, range = 400..)]
+                Success = 200,
+            }
+        };
+
+        let repr_type: Ident = parse_quote! { u16 };
+        let result = parse_repr_cast(repr_type, Default::default(), input).unwrap();
+        let ok = &result.variants[0];
+        assert_eq!(ok.attributes.len(), 1);
+        assert!(ok.attributes[0].path().is_ident("doc"));
+        assert_eq!(ok.options.accepts[0].bounds, Some((0, 0)));
+        assert!(ok.options.accepts[0].is_alias());
+
+        let ranges = &result.variants[1].options.accepts;
+        assert_eq!(ranges[0].bounds, Some((200, 299)));
+        assert_eq!(ranges[1].bounds, Some((400, 65535)));
+        // The upper bound of `400..` is the maximum of the repr type
+        assert!(ranges[1].end.is_none());
+    }
+
+    #[test]
+    fn test_parse_variant_options() {
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                #[repr_cast(rename = "pending", hidden)]
+                Pending,
+                #[repr_cast(skip)]
+                Legacy,
+            }
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let result = parse_repr_cast(repr_type, Default::default(), input).unwrap();
+        let pending = &result.variants[0].options;
+        assert_eq!(pending.rename.as_ref().unwrap().value(), "pending");
+        assert!(pending.hidden && !pending.skip);
+        assert!(result.variants[1].options.skip);
+        assert!(result.variants.iter().all(|v| v.attributes.is_empty()));
+        // `rename` and `hidden` need the variant lists and names
+        assert!(result.options.variants);
+
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                #[repr_cast(skip)]
+                Legacy,
+            }
+        };
+        let repr_type: Ident = parse_quote! { u8 };
+        let result = parse_repr_cast(repr_type, Default::default(), input).unwrap();
+        assert!(!result.options.variants);
+    }
+
+    #[test]
+    fn test_parse_variant_options_errors() {
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                #[repr_cast(rename = "pending", frobnicate)]
+                Pending,
+            }
+        };
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, Default::default(), input).unwrap_err();
+        assert_eq!(err.to_string(), "unknown repr_cast variant option `frobnicate`");
+
+        let input: DeriveInput = parse_quote! {
+            enum Status {
+                #[repr_cast(skip, alias = 3)]
+                Pending,
+            }
+        };
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, Default::default(), input).unwrap_err();
+        assert!(err.to_string().contains("cannot have aliases or ranges"));
+    }
+
+    #[test]
+    fn test_parse_auto_repr() {
+        let input: DeriveInput = parse_quote! {
+            enum Code {
+                A = 255,
+                B,
+            }
+        };
+        let result = parse_repr_cast(parse_quote! { auto }, Default::default(), input).unwrap();
+        assert_eq!(result.repr_type.to_string(), "u16");
+        assert!(result.options.auto);
+
+        let input: DeriveInput = parse_quote! {
+            enum Code {
+                A = -128,
+                B = 127,
+            }
+        };
+        let result = parse_repr_cast(parse_quote! { auto_signed }, Default::default(), input).unwrap();
+        assert_eq!(result.repr_type.to_string(), "i8");
+        assert_eq!(result.variants[0].value, Some(-128));
+    }
+
+    #[test]
+    fn test_parse_auto_repr_errors() {
+        let input: DeriveInput = parse_quote! {
+            enum Code {
+                A = -1,
+            }
+        };
+        let err = parse_repr_cast(parse_quote! { auto }, Default::default(), input).unwrap_err();
+        assert!(err.to_string().contains("use `auto_signed`"));
+
+        let input: DeriveInput = parse_quote! {
+            enum Code {
+                A = BASE,
+            }
+        };
+        let err = parse_repr_cast(parse_quote! { auto_signed }, Default::default(), input).unwrap_err();
+        assert!(err.to_string().contains("the discriminant of `A` cannot be evaluated"));
+    }
+
+    #[test]
+    fn test_parse_cfg_variants() {
+        let input: DeriveInput = parse_quote! {
+            enum Gated {
+                First,
+                #[cfg(feature = "x")]
+                Second,
+                Third,
+                #[cfg_attr(feature = "y", cfg(feature = "z"), derive(Debug))]
+                #[cfg_attr(feature = "y", doc = "not a cfg")]
+                Fourth = 10,
+                Fifth,
+                Sixth = 20,
+                Seventh,
+            }
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let result = parse_repr_cast(repr_type, Default::default(), input).unwrap();
+        let cfg: Vec<_> = result.variants.iter().map(|v| v.cfg.len()).collect();
+        assert_eq!(cfg, [0, 1, 0, 1, 0, 0, 0]);
+        let reduced = &result.variants[3].cfg[0];
+        assert_eq!(
+            quote::quote!(#reduced).to_string(),
+            "# [cfg_attr (feature = \"y\" , cfg (feature = \"z\"))]"
+        );
+        // All attributes are kept on the variants themselves
+        assert_eq!(result.variants[3].attributes.len(), 2);
+
+        // Gated variants and the implicit ones following them are left to the compiler
+        let values: Vec<_> = result.variants.iter().map(|v| v.value).collect();
+        assert_eq!(values, [Some(0), None, None, None, None, Some(20), Some(21)]);
+    }
+
+    #[test]
+    fn test_parse_variant_overlapping_values() {
+        let input: DeriveInput = parse_quote! {
+            enum Reply {
+                #[repr_cast(alias = 200)]
+                Ok = 1,
+                #[repr_cast(range = 200..=299)]
+                Success = 200,
+            }
+        };
+
+        let repr_type: Ident = parse_quote! { u16 };
+        let err = parse_repr_cast(repr_type, Default::default(), input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "alias `200` of `Ok` overlaps the discriminant of `Success`"
+        );
+
+        let input: DeriveInput = parse_quote! {
+            enum Reply {
+                #[repr_cast(range = 300..200)]
+                Ok = 1,
+            }
+        };
+        let repr_type: Ident = parse_quote! { u16 };
+        let err = parse_repr_cast(repr_type, Default::default(), input).unwrap_err();
+        assert_eq!(err.to_string(), "range of `Ok` is empty");
+    }
+
+    #[test]
+    fn test_parse_args_strict() {
+        let args = parse_args(quote::quote! { u8, strict }).unwrap();
+        assert!(args.options.strict);
+    }
+
+    #[test]
+    fn test_parse_args_companion_types() {
+        let args = parse_args(quote::quote! { u8, flags, set, map, packed, atomic, open }).unwrap();
+        assert!(args.options.flags);
+        assert!(args.options.set);
+        assert!(args.options.map);
+        assert!(args.options.packed);
+        assert!(args.options.atomic);
+        assert!(args.options.open);
+    }
+
+    #[test]
+    fn test_parse_args_codec() {
+        let args = parse_args(quote::quote! { u8, codec }).unwrap();
+        assert!(args.options.codec);
+        assert!(!args.options.codec_big_endian);
+
+        let args = parse_args(quote::quote! { u16, codec(big_endian) }).unwrap();
+        assert!(args.options.codec);
+        assert!(args.options.codec_big_endian);
+
+        let err = parse_args(quote::quote! { u16, codec(network) }).unwrap_err();
+        assert!(err.to_string().contains("unknown codec option `network`"));
+    }
+
+    #[test]
+    fn test_parse_codec_rejects_usize_and_generics() {
+        let options = ReprOptions {
+            codec: true,
+            ..Default::default()
+        };
+
+        let input: DeriveInput = parse_quote! {
+            enum Message {
+                Ping,
+                Data(u32),
+            }
+        };
+        let repr_type: Ident = parse_quote! { usize };
+        let err = parse_repr_cast(repr_type, options.clone(), input).unwrap_err();
+        assert!(err.to_string().contains("requires a fixed-width repr type, not `usize`"));
+
+        let input: DeriveInput = parse_quote! {
+            enum Message<T> {
+                Ping,
+                Data(T),
+            }
+        };
+        let repr_type: Ident = parse_quote! { u8 };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("doesn't support generic enums"));
+    }
+
+    #[test]
+    fn test_parse_args_compose() {
+        let args = parse_args(quote::quote! { u16, compose(hi = Category, lo = codes::Code) }).unwrap();
+        let compose = args.options.compose.unwrap();
+        assert!(compose.parts.is_empty());
+        assert!(compose.hi.is_ident("Category"));
+        assert_eq!(path_to_string(&compose.lo), "codes::Code");
+
+        let err = parse_args(quote::quote! { u16, compose(hi = Category) }).unwrap_err();
+        assert!(err.to_string().contains("requires both `hi = Enum` and `lo = Enum`"));
+
+        let err = parse_args(quote::quote! { u16, compose(hi = a::Code, lo = b::Code) }).unwrap_err();
+        assert!(err.to_string().contains("enums of different names"));
+    }
+
+    #[test]
+    fn test_parse_compose_discriminants() {
+        let input: DeriveInput = parse_quote! {
+            enum ErrorCode {
+                NotFound = (Io, Missing),
+                Denied = (Category::Io, Code::Denied),
+                Timeout,
+                Unreachable = 0x0201,
+            }
+        };
+        let options = ReprOptions {
+            compose: Some(Compose {
+                hi: parse_quote! { Category },
+                lo: parse_quote! { Code },
+                parts: vec![],
+            }),
+            ..Default::default()
+        };
+
+        let repr_type: Ident = parse_quote! { u16 };
+        let repr_enum = parse_repr_cast(repr_type, options.clone(), input).unwrap();
+        let parts = &repr_enum.options.compose.as_ref().unwrap().parts;
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].0, "NotFound");
+        assert_eq!(parts[1].2, parse_quote! { Code::Denied });
+        let expected: Expr = parse_quote! {
+            ((Category::Io.as_repr() as u16) << 8) | ((Code::Missing.as_repr() as u16) & (((1 as u16) << 8) - 1))
+        };
+        assert_eq!(repr_enum.variants[0].discriminant, Some(expected));
+        let discriminant = repr_enum.variants[1].discriminant.as_ref().unwrap();
+        assert!(quote::quote! { #discriminant }.to_string().contains("Code :: Denied . as_repr ()"));
+        assert!(matches!(
+            repr_enum.variants[2].calculated_discriminant,
+            CalculatedDiscriminant::Relative { offset: 1, .. }
+        ));
+        assert_eq!(repr_enum.variants[3].value, Some(0x0201));
+
+        let input: DeriveInput = parse_quote! {
+            enum ErrorCode {
+                NotFound = (Io, Missing, Extra),
+            }
+        };
+        let repr_type: Ident = parse_quote! { u16 };
+        let err = parse_repr_cast(repr_type, options.clone(), input).unwrap_err();
+        assert!(err.to_string().contains("must be a `(high, low)` pair"));
+
+        let input: DeriveInput = parse_quote! {
+            enum ErrorCode {
+                NotFound = (Io, Missing),
+            }
+        };
+        let repr_type: Ident = parse_quote! { auto };
+        let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+        assert!(err.to_string().contains("can't be used with `auto`"));
+    }
+
+    #[test]
+    fn test_parse_args_mask() {
+        let args = parse_args(quote::quote! { u8, mask = 0x0F }).unwrap();
+        assert!(args.options.mask.is_some());
+        assert!(!args.options.mask_try_from);
+
+        let args = parse_args(quote::quote! { u8, mask(0x0F, try_from) }).unwrap();
+        assert!(args.options.mask.is_some());
+        assert!(args.options.mask_try_from);
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse_args(quote::quote! { u8, ascii }).unwrap();
+        assert_eq!(args.repr_type.unwrap().to_string(), "u8");
+        assert!(args.options.ascii);
+
+        // The repr type may be omitted in favor of an existing #[repr(...)]
+        let args = parse_args(quote::quote! { ascii }).unwrap();
+        assert!(args.repr_type.is_none());
+        assert!(args.options.ascii);
     }
 
     #[test]
-    fn test_try_evaluate_expr_complex() {
-        let expr: syn::Expr = parse_quote! { 1 + 2 };
-        let result = try_evaluate_expr(&expr);
+    fn test_parse_args_also() {
+        let args = parse_args(quote::quote! { u8, also(u16, i64) }).unwrap();
+        let also: Vec<String> = args.options.also.iter().map(|ty| ty.to_string()).collect();
+        assert_eq!(also, ["u16", "i64"]);
+    }
+
+    #[test]
+    fn test_parse_also_rejects_invalid_types() {
+        for (also, message) in [
+            (quote::quote! { u8 }, "already the repr type"),
+            (quote::quote! { f32 }, "primitive integer types"),
+            (quote::quote! { u16, u16 }, "listed more than once"),
+        ] {
+            let input: DeriveInput = parse_quote! {
+                enum Test {
+                    A,
+                }
+            };
+            let options = ReprOptions {
+                also: syn::parse::Parser::parse2(
+                    syn::punctuated::Punctuated::<Ident, syn::Token![,]>::parse_terminated,
+                    also,
+                )
+                .unwrap()
+                .into_iter()
+                .collect(),
+                ..Default::default()
+            };
+
+            let repr_type: Ident = parse_quote! { u8 };
+            let err = parse_repr_cast(repr_type, options, input).unwrap_err();
+            assert!(err.to_string().contains(message));
+        }
+    }
+
+    #[test]
+    fn test_parse_args_newtype() {
+        let args = parse_args(quote::quote! { u16, newtype = wire::Opcode }).unwrap();
+        let newtype = args.options.newtype.unwrap();
+        let ty = &newtype.ty;
+        assert_eq!(quote::quote!(#ty).to_string(), "wire :: Opcode");
+        assert!(newtype.new.is_none());
+        assert!(newtype.get.is_none());
+
+        let args = parse_args(quote::quote! { u16, newtype(Opcode, new = new, get = get) }).unwrap();
+        let newtype = args.options.newtype.unwrap();
+        assert_eq!(newtype.new.unwrap().to_string(), "new");
+        assert_eq!(newtype.get.unwrap().to_string(), "get");
+    }
+
+    #[test]
+    fn test_parse_args_newtype_rejects_unknown_key() {
+        let err = parse_args(quote::quote! { u16, newtype(Opcode, set = set) }).unwrap_err();
+        assert!(err.to_string().contains("unknown newtype option `set`"));
+    }
+
+    #[test]
+    fn test_parse_args_rejects_unknown_option() {
+        let err = parse_args(quote::quote! { u8, bogus }).unwrap_err();
+        assert!(err.to_string().contains("unknown repr_cast option `bogus`"));
+    }
+
+    #[test]
+    fn test_parse_rejects_non_enum() {
+        let input: DeriveInput = parse_quote! {
+            struct NotAnEnum {
+                field: u32,
+            }
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let result = parse_repr_cast(repr_type, Default::default(), input);
+
         assert!(result.is_err());
+        let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("can only be applied to enums"));
+    }
+
+    #[test]
+    fn test_parse_enum_with_fields() {
+        let input: DeriveInput = parse_quote! {
+            enum WithFields {
+                Variant1(u32) = 3,
+                Variant2,
+            }
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let repr_enum = parse_repr_cast(repr_type, Default::default(), input).unwrap();
+
+        assert!(repr_enum.variants[0].has_fields());
+        assert!(!repr_enum.variants[1].has_fields());
+        assert_eq!(repr_enum.variants[1].value, Some(4));
+    }
+
+    #[test]
+    fn test_parse_rejects_enum_with_fields_for_other() {
+        let input: DeriveInput = parse_quote! {
+            enum WithFields {
+                Variant1(u32),
+                Variant2,
+            }
+        };
+
+        let repr_type: Ident = parse_quote! { u8 };
+        let options = ReprOptions {
+            other: Some(parse_quote! { Unknown }),
+            ..Default::default()
+        };
+        let result = parse_repr_cast(repr_type, options, input);
+
+        let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("`other` option can only be applied to fieldless enums"));
+    }
+
+    #[test]
+    fn test_extract_repr_from_attrs() {
+        let input: DeriveInput = parse_quote! {
+            #[repr(u32)]
+            enum Test {
+                A,
+            }
+        };
+
+        let result = extract_repr_from_attrs(&input).unwrap();
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().to_string(), "u32");
+    }
+
+    #[test]
+    fn test_extract_repr_none() {
+        let input: DeriveInput = parse_quote! {
+            enum Test {
+                A,
+            }
+        };
+
+        let result = extract_repr_from_attrs(&input).unwrap();
+        assert!(result.is_none());
     }
 
     #[test]
@@ -348,7 +2337,7 @@ mod tests {
         };
 
         let repr_type: Ident = parse_quote! { u8 };
-        let result = parse_repr_cast(repr_type, input);
+        let result = parse_repr_cast(repr_type, Default::default(), input);
 
         assert!(result.is_ok());
         let repr_enum = result.unwrap();
@@ -366,7 +2355,7 @@ mod tests {
         };
 
         let repr_type: Ident = parse_quote! { u8 };
-        let result = parse_repr_cast(repr_type, input);
+        let result = parse_repr_cast(repr_type, Default::default(), input);
 
         assert!(result.is_ok());
         let repr_enum = result.unwrap();
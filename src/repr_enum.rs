@@ -1,8 +1,11 @@
 //! Data structures representing a parsed enum suitable for repr_cast code generation.
 
-use syn::{Attribute, Expr, Generics, Ident, Visibility};
+use syn::{Attribute, Expr, Fields, Generics, Ident, Path, Visibility};
 
-/// Represents a fieldless enum that has been parsed and validated for repr_cast.
+/// Represents an enum that has been parsed and validated for repr_cast.
+///
+/// Enums whose variants carry fields get their conversions through a
+/// generated fieldless `{Name}Tag` enum.
 #[derive(Debug, Clone)]
 pub struct ReprEnum {
     /// The name of the enum
@@ -13,10 +16,100 @@ pub struct ReprEnum {
     pub visibility: Visibility,
     /// Attributes applied to the enum (excluding repr and repr_cast)
     pub attributes: Vec<Attribute>,
-    /// Generic parameters (currently not supported, kept for future extension)
+    /// Generic parameters, which only enums with fields can use
     pub generics: Generics,
     /// The variants of the enum
     pub variants: Vec<EnumVariant>,
+    /// Options given in the `#[repr_cast(...)]` attribute
+    pub options: ReprOptions,
+}
+
+/// Enum-level options given in the `#[repr_cast(...)]` attribute after the repr type.
+#[derive(Debug, Clone, Default)]
+pub struct ReprOptions {
+    /// The repr type was chosen by the macro (`auto` or `auto_signed`)
+    pub auto: bool,
+    /// Generate char conversions and a character `Display` (`ascii`)
+    pub ascii: bool,
+    /// Additional integer types to convert to and from (`also(u16, u32)`)
+    pub also: Vec<Ident>,
+    /// A user newtype around the repr type to convert to and from (`newtype = Opcode`)
+    pub newtype: Option<Newtype>,
+    /// Sentinel value that encodes `None` for `Option<Enum>` (`none = 0xFF`)
+    pub none: Option<Expr>,
+    /// Number of bits the values occupy when packed into a larger word (`bits = 3`)
+    pub bits: Option<syn::LitInt>,
+    /// Mask of the bits that carry the value; other bits are ignored by
+    /// `from_repr_masked` (`mask = 0x0F`)
+    pub mask: Option<Expr>,
+    /// Apply `mask` in the `TryFrom` conversions as well (`mask(0x0F, try_from)`)
+    pub mask_try_from: bool,
+    /// Values or ranges reserved for future use (`reserved(0x10..=0x1F, 0xFF)`).
+    /// When non-empty, the conversion error distinguishes reserved values from
+    /// unknown ones.
+    pub reserved: Vec<Expr>,
+    /// Generate `COUNT`, `VARIANTS`, `iter()`, `name()`, `is_deprecated()`,
+    /// `ordinal()` and `from_ordinal()` (`variants`, or set during parsing for
+    /// the options that use them)
+    pub variants: bool,
+    /// Reject deprecated variants in the `TryFrom` conversions (`strict`)
+    pub strict: bool,
+    /// Every discriminant is a single bit; generate a `{Name}Flags` set type (`flags`)
+    pub flags: bool,
+    /// Generate a `{Name}Set` bitset type indexed by variant ordinal (`set`)
+    pub set: bool,
+    /// Generate a `{Name}Map<T>` array type indexed by variant (`map`)
+    pub map: bool,
+    /// Generate `{Name}PackedVec` and `{Name}PackedArray` bit-packed storage (`packed`)
+    pub packed: bool,
+    /// Generate an `Atomic{Name}` wrapper around the matching atomic integer (`atomic`)
+    pub atomic: bool,
+    /// Turn the enum into a newtype that preserves unknown values, with the
+    /// enum itself renamed to `{Name}Known` (`open`)
+    pub open: bool,
+    /// A catch-all variant added to the enum, carrying the values that match no
+    /// other variant (`other = Unknown`)
+    pub other: Option<Ident>,
+    /// Generate `encode` and `decode` writing the tag followed by the fields
+    /// (`codec`)
+    pub codec: bool,
+    /// Write the tag and fields of `codec` in big-endian byte order
+    /// (`codec(big_endian)`)
+    pub codec_big_endian: bool,
+    /// Sub-enums making up the high and low halves of the discriminants
+    /// (`compose(hi = Category, lo = Code)`)
+    pub compose: Option<Compose>,
+    /// Generate `NonZero` conversions (`nonzero`, or detected during parsing
+    /// when every discriminant is known and none is zero)
+    pub nonzero: bool,
+    /// Don't detect `NonZero` conversions (`nonzero = false`)
+    pub nonzero_disabled: bool,
+}
+
+/// A user newtype around the repr type, such as `struct Opcode(u16)`.
+#[derive(Debug, Clone)]
+pub struct Newtype {
+    /// The newtype itself
+    pub ty: Path,
+    /// Associated function building the newtype from the repr value;
+    /// the tuple constructor `Ty(value)` is used if not given
+    pub new: Option<Ident>,
+    /// Method returning the repr value; the field `.0` is used if not given
+    pub get: Option<Ident>,
+}
+
+/// The sub-enums of a composed enum, such as `compose(hi = Category, lo = Code)`.
+/// Each discriminant is the high part shifted by half the width of the repr
+/// type, combined with the low part.
+#[derive(Debug, Clone)]
+pub struct Compose {
+    /// The enum of the high half
+    pub hi: Path,
+    /// The enum of the low half
+    pub lo: Path,
+    /// The variants whose discriminant is written as a `(Hi, Lo)` pair, with
+    /// the two parts, checked to fit in their halves
+    pub parts: Vec<(Ident, Expr, Expr)>,
 }
 
 /// Represents a single variant in the enum.
@@ -24,41 +117,275 @@ pub struct ReprEnum {
 pub struct EnumVariant {
     /// The name of the variant
     pub name: Ident,
+    /// The fields of the variant, `Fields::Unit` for a unit variant
+    pub fields: Fields,
     /// Attributes applied to this variant
     pub attributes: Vec<Attribute>,
+    /// The `cfg` attributes of this variant, and its `cfg_attr` attributes
+    /// reduced to the `cfg`s they contain, to repeat on generated code
+    /// referring to the variant
+    pub cfg: Vec<Attribute>,
     /// The discriminant value, if explicitly specified
     pub discriminant: Option<Expr>,
+    /// The discriminant value, if it could be evaluated at expansion time
+    pub value: Option<i128>,
+    /// Options given in a `#[repr_cast(...)]` attribute on the variant
+    pub options: VariantOptions,
     /// The calculated discriminant value (either explicit or implicit)
-    /// Note: This field is primarily used for testing the parse logic.
-    /// Code generation uses direct variant casts instead.
-    #[allow(dead_code)]
     pub calculated_discriminant: CalculatedDiscriminant,
 }
 
+impl EnumVariant {
+    /// Returns `true` if the variant has a `#[deprecated]` attribute.
+    pub fn is_deprecated(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attr| attr.path().is_ident("deprecated"))
+    }
+
+    /// Returns `true` if the variant carries fields.
+    pub fn has_fields(&self) -> bool {
+        !matches!(self.fields, Fields::Unit)
+    }
+}
+
+/// Variant-level options given in a `#[repr_cast(...)]` attribute on the variant.
+#[derive(Debug, Clone, Default)]
+pub struct VariantOptions {
+    /// Values accepted by `from_repr` besides the discriminant
+    /// (`alias = 0`, `range = 200..=299`)
+    pub accepts: Vec<AcceptedValues>,
+    /// The variant is never produced by `from_repr` (`skip`)
+    pub skip: bool,
+    /// The name returned by `name()` instead of the variant name (`rename = "..."`)
+    pub rename: Option<syn::LitStr>,
+    /// The variant is excluded from `VARIANTS` and `iter()` (`hidden`)
+    pub hidden: bool,
+}
+
+/// An alias or a range of values decoded to a variant in addition to its discriminant.
+#[derive(Debug, Clone)]
+pub struct AcceptedValues {
+    /// The expression as written: a value for `alias`, a range for `range`
+    pub source: Expr,
+    /// The first accepted value, or `None` if it is the minimum of the repr type
+    pub start: Option<Expr>,
+    /// The last accepted value (inclusive), or `None` if it is the maximum of the repr type
+    pub end: Option<Expr>,
+    /// The first and last accepted values, if known at expansion time
+    pub bounds: Option<(i128, i128)>,
+}
+
+impl AcceptedValues {
+    /// Returns `true` for `alias = value`, `false` for `range = start..=end`.
+    pub fn is_alias(&self) -> bool {
+        !matches!(self.source, Expr::Range(_))
+    }
+
+    /// Describes the values for error messages, e.g. ``range `200..=299` of `Success` ``.
+    pub fn describe(&self, variant: &Ident) -> String {
+        let source = &self.source;
+        let kind = if self.is_alias() { "alias" } else { "range" };
+        format!(
+            "{} `{}` of `{}`",
+            kind,
+            quote::quote!(#source).to_string().replace(' ', ""),
+            variant
+        )
+    }
+}
+
+/// A primitive integer type that can be used as an enum representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntType {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+}
+
+impl IntType {
+    /// All supported integer types, unsigned first, in increasing width.
+    pub const ALL: [IntType; 12] = [
+        IntType::U8,
+        IntType::U16,
+        IntType::U32,
+        IntType::U64,
+        IntType::U128,
+        IntType::Usize,
+        IntType::I8,
+        IntType::I16,
+        IntType::I32,
+        IntType::I64,
+        IntType::I128,
+        IntType::Isize,
+    ];
+
+    /// Looks up the integer type named by `ident`, if any.
+    pub fn from_ident(ident: &Ident) -> Option<Self> {
+        Self::ALL.into_iter().find(|ty| ident == ty.name())
+    }
+
+    /// The Rust name of this type (e.g., `"u8"`).
+    pub fn name(self) -> &'static str {
+        match self {
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::Usize => "usize",
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Isize => "isize",
+        }
+    }
+
+    /// Width of the type in bits.
+    ///
+    /// `usize` and `isize` are assumed to be 64 bits wide; the compiler still
+    /// checks the generated code against the real target width.
+    pub fn bits(self) -> u32 {
+        match self {
+            IntType::U8 | IntType::I8 => 8,
+            IntType::U16 | IntType::I16 => 16,
+            IntType::U32 | IntType::I32 => 32,
+            IntType::U64 | IntType::I64 | IntType::Usize | IntType::Isize => 64,
+            IntType::U128 | IntType::I128 => 128,
+        }
+    }
+
+    /// Whether the type is signed.
+    pub fn is_signed(self) -> bool {
+        matches!(
+            self,
+            IntType::I8 | IntType::I16 | IntType::I32 | IntType::I64 | IntType::I128 | IntType::Isize
+        )
+    }
+
+    /// Smallest value of the type.
+    pub fn min_value(self) -> i128 {
+        if self.is_signed() {
+            i128::MIN >> (128 - self.bits())
+        } else {
+            0
+        }
+    }
+
+    /// Largest value of the type that can be tracked.
+    ///
+    /// Values are tracked as `i128`, so for `u128` this is `i128::MAX`: larger
+    /// `u128` values are never evaluated, and are left to the compiler.
+    pub fn max_value(self) -> i128 {
+        match self {
+            IntType::I128 | IntType::U128 => i128::MAX,
+            _ => (1i128 << (self.bits() - u32::from(self.is_signed()))) - 1,
+        }
+    }
+
+    /// Whether `value` is representable in this type.
+    pub fn contains(self, value: i128) -> bool {
+        (self.min_value()..=self.max_value()).contains(&value)
+    }
+
+    /// Whether every value of this type converts losslessly to `target` on
+    /// every platform, mirroring the `From` impls of the standard library.
+    pub fn converts_losslessly_to(self, target: IntType) -> bool {
+        use IntType::*;
+        match (self, target) {
+            _ if self == target => true,
+            (Usize | Isize, _) => false,
+            (_, Usize) => matches!(self, U8 | U16),
+            (_, Isize) => matches!(self, U8 | I8 | I16),
+            _ if self.is_signed() => target.is_signed() && target.bits() >= self.bits(),
+            _ => target.bits() > self.bits() || (!target.is_signed() && target.bits() == self.bits()),
+        }
+    }
+
+    /// The name of the matching `core::num::NonZero*` type (e.g., `"NonZeroU8"`).
+    pub fn nonzero_name(self) -> String {
+        let name = self.name();
+        format!("NonZero{}{}", name[..1].to_uppercase(), &name[1..])
+    }
+
+    /// The name of the matching `core::sync::atomic` type (e.g., `"AtomicU8"`).
+    pub fn atomic_name(self) -> String {
+        let name = self.name();
+        format!("Atomic{}{}", name[..1].to_uppercase(), &name[1..])
+    }
+
+    /// The `target_has_atomic` value under which the atomic type exists
+    /// (e.g., `"8"`, or `"ptr"` for `usize` and `isize`).
+    pub fn atomic_width(self) -> &'static str {
+        match self {
+            IntType::U8 | IntType::I8 => "8",
+            IntType::U16 | IntType::I16 => "16",
+            IntType::U32 | IntType::I32 => "32",
+            IntType::U64 | IntType::I64 => "64",
+            IntType::U128 | IntType::I128 => "128",
+            IntType::Usize | IntType::Isize => "ptr",
+        }
+    }
+
+    /// Converts `value` to this type with the semantics of an `as` cast.
+    pub fn wrap(self, value: i128) -> i128 {
+        match (self.bits(), self.is_signed()) {
+            (128, _) => value,
+            (bits, false) => value & ((1i128 << bits) - 1),
+            (bits, true) => (value << (128 - bits)) >> (128 - bits),
+        }
+    }
+}
+
 /// Represents the calculated discriminant for a variant.
-/// Note: This type is primarily used for testing parse logic.
-/// Code generation now uses direct variant casts (e.g., `Variant as Type`)
-/// instead of pattern matching on discriminant values.
+///
+/// Discriminants are modeled as "expression + offset": an implicit variant that
+/// follows an explicit expression which cannot be evaluated at expansion time
+/// (e.g. `B = BASE + 1`) is tracked relative to that expression rather than
+/// being given a guessed value.
+///
+/// Note: Code generation uses direct variant casts (e.g., `Variant as Type`)
+/// for the core conversions. Enums with an `other` variant can't be cast and
+/// go through [`CalculatedDiscriminant::as_value_tokens`] instead, so the
+/// compiler still does the evaluation.
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub enum CalculatedDiscriminant {
     /// An explicit discriminant expression from the source
     Explicit(Expr),
     /// An implicit discriminant (the calculated integer value)
     Implicit(i128),
+    /// An implicit discriminant that follows an explicit expression which could
+    /// not be evaluated, with the value `base + offset`
+    Relative { base: Expr, offset: i128 },
 }
 
 impl CalculatedDiscriminant {
-    /// Returns a token stream representing this discriminant value suitable for pattern matching.
-    /// Note: This method is kept for testing purposes but is no longer used in code generation.
-    #[allow(dead_code)]
-    pub fn as_pattern_tokens(&self) -> proc_macro2::TokenStream {
+    /// Returns a constant expression evaluating to this discriminant value.
+    ///
+    /// The expression is untyped and takes its type from the context it is
+    /// used in, which is expected to be the repr type of the enum.
+    pub fn as_value_tokens(&self) -> proc_macro2::TokenStream {
         match self {
             CalculatedDiscriminant::Explicit(expr) => quote::quote! { #expr },
             CalculatedDiscriminant::Implicit(value) => {
                 let lit = proc_macro2::Literal::i128_unsuffixed(*value);
                 quote::quote! { #lit }
             }
+            CalculatedDiscriminant::Relative { base, offset } => {
+                let lit = proc_macro2::Literal::i128_unsuffixed(*offset);
+                quote::quote! { (#base) + #lit }
+            }
         }
     }
 }
@@ -68,25 +395,91 @@ mod tests {
     use super::*;
     use syn::parse_quote;
 
+    #[test]
+    fn test_int_type_ranges() {
+        assert_eq!(IntType::U8.max_value(), 255);
+        assert_eq!(IntType::I8.min_value(), -128);
+        assert_eq!(IntType::I8.max_value(), 127);
+        assert_eq!(IntType::U128.max_value(), i128::MAX);
+        assert_eq!(IntType::I128.min_value(), i128::MIN);
+        assert_eq!(IntType::I128.max_value(), i128::MAX);
+        assert!(IntType::U16.contains(65535));
+        assert!(!IntType::U16.contains(-1));
+    }
+
+    #[test]
+    fn test_int_type_wrap() {
+        assert_eq!(IntType::U8.wrap(300), 44);
+        assert_eq!(IntType::U8.wrap(-1), 255);
+        assert_eq!(IntType::I8.wrap(200), -56);
+        assert_eq!(IntType::I16.wrap(-5), -5);
+    }
+
+    #[test]
+    fn test_int_type_lossless_conversions() {
+        assert!(IntType::U8.converts_losslessly_to(IntType::U16));
+        assert!(IntType::U8.converts_losslessly_to(IntType::I16));
+        assert!(IntType::U16.converts_losslessly_to(IntType::Usize));
+        assert!(IntType::I16.converts_losslessly_to(IntType::Isize));
+        assert!(IntType::U32.converts_losslessly_to(IntType::I64));
+        assert!(!IntType::U8.converts_losslessly_to(IntType::I8));
+        assert!(!IntType::I8.converts_losslessly_to(IntType::U64));
+        assert!(!IntType::U32.converts_losslessly_to(IntType::Usize));
+        assert!(!IntType::Usize.converts_losslessly_to(IntType::U64));
+        assert!(!IntType::U16.converts_losslessly_to(IntType::U8));
+    }
+
+    #[test]
+    fn test_int_type_nonzero_name() {
+        assert_eq!(IntType::U8.nonzero_name(), "NonZeroU8");
+        assert_eq!(IntType::Isize.nonzero_name(), "NonZeroIsize");
+    }
+
+    #[test]
+    fn test_int_type_atomic() {
+        assert_eq!(IntType::U8.atomic_name(), "AtomicU8");
+        assert_eq!(IntType::I64.atomic_name(), "AtomicI64");
+        assert_eq!(IntType::I16.atomic_width(), "16");
+        assert_eq!(IntType::Usize.atomic_width(), "ptr");
+    }
+
+    #[test]
+    fn test_int_type_from_ident() {
+        let ident: Ident = parse_quote! { u16 };
+        assert_eq!(IntType::from_ident(&ident), Some(IntType::U16));
+        let ident: Ident = parse_quote! { C };
+        assert_eq!(IntType::from_ident(&ident), None);
+    }
+
     #[test]
     fn test_calculated_discriminant_explicit() {
         let expr: Expr = parse_quote! { 42 };
         let disc = CalculatedDiscriminant::Explicit(expr);
-        let tokens = disc.as_pattern_tokens();
+        let tokens = disc.as_value_tokens();
         assert_eq!(tokens.to_string(), "42");
     }
 
     #[test]
     fn test_calculated_discriminant_implicit() {
         let disc = CalculatedDiscriminant::Implicit(10);
-        let tokens = disc.as_pattern_tokens();
+        let tokens = disc.as_value_tokens();
         assert_eq!(tokens.to_string(), "10");
     }
 
     #[test]
     fn test_calculated_discriminant_negative() {
         let disc = CalculatedDiscriminant::Implicit(-5);
-        let tokens = disc.as_pattern_tokens();
+        let tokens = disc.as_value_tokens();
         assert_eq!(tokens.to_string(), "- 5");
     }
+
+    #[test]
+    fn test_calculated_discriminant_relative() {
+        let disc = CalculatedDiscriminant::Relative {
+            base: parse_quote! { BASE + 1 },
+            offset: 2,
+        };
+        let tokens = disc.as_value_tokens();
+        assert_eq!(tokens.to_string(), "(BASE + 1) + 2");
+    }
 }
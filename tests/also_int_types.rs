@@ -0,0 +1,78 @@
+use repr_cast::repr_cast;
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8, also(u16, u32, i64, usize, i8))]
+enum Status {
+    Pending = 0,
+    Active = 1,
+    Completed = 200,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(i16, also(i8, u64))]
+enum Level {
+    Low = -300,
+    Zero = 0,
+    High = 300,
+}
+
+#[test]
+fn test_lossless_from_enum() {
+    assert_eq!(u16::from(Status::Completed), 200);
+    assert_eq!(u32::from(Status::Active), 1);
+    assert_eq!(i64::from(&Status::Completed), 200);
+    assert_eq!(usize::from(Status::Pending), 0);
+
+    let wide: u32 = Status::Completed.into();
+    assert_eq!(wide, 200);
+}
+
+#[test]
+fn test_lossy_try_from_enum() {
+    assert_eq!(i8::try_from(Status::Active), Ok(1));
+    assert!(i8::try_from(Status::Completed).is_err());
+    assert!(i8::try_from(&Status::Completed).is_err());
+
+    assert!(i8::try_from(Level::High).is_err());
+    assert_eq!(i8::try_from(Level::Zero), Ok(0));
+    assert!(u64::try_from(Level::Low).is_err());
+    assert_eq!(u64::try_from(Level::High), Ok(300));
+}
+
+#[test]
+fn test_try_from_wider_integer() {
+    assert_eq!(Status::try_from(200u16).unwrap(), Status::Completed);
+    assert_eq!(Status::try_from(1u32).unwrap(), Status::Active);
+    assert_eq!(Status::try_from(0i64).unwrap(), Status::Pending);
+    assert_eq!(Status::try_from(&200usize).unwrap(), Status::Completed);
+    assert_eq!(Level::try_from(300u64).unwrap(), Level::High);
+}
+
+#[test]
+fn test_error_carries_original_value() {
+    // Out of range for the repr type
+    let err: StatusIntConversionError<u32> = Status::try_from(1000u32).unwrap_err();
+    assert_eq!(err.0, 1000u32);
+    assert_eq!(err.to_string(), "unknown Status variant: 1000");
+
+    let err = Status::try_from(-1i64).unwrap_err();
+    assert_eq!(err.0, -1i64);
+
+    let err = Level::try_from(u64::MAX).unwrap_err();
+    assert_eq!(err.0, u64::MAX);
+
+    // In range, but not a variant
+    let err = Status::try_from(&7u16).unwrap_err();
+    assert_eq!(err.0, 7u16);
+
+    let err = Status::try_from(-5i8).unwrap_err();
+    assert_eq!(err.0, -5i8);
+}
+
+#[test]
+fn test_repr_conversions_unchanged() {
+    assert_eq!(Status::try_from(200u8).unwrap(), Status::Completed);
+    let err: StatusConversionError = Status::try_from(3u8).unwrap_err();
+    assert_eq!(err.0, 3u8);
+}
@@ -0,0 +1,83 @@
+use repr_cast::repr_cast;
+use std::convert::TryFrom;
+
+const QUIT: u8 = b'Q';
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8, ascii)]
+enum Command {
+    Get = 'G',
+    Head,         // 'H'
+    Put = b'P',
+    Quit = QUIT,
+    Wait,         // 'R'
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u16)]
+enum Symbol {
+    Alpha = 'α',
+    Beta,
+}
+
+#[test]
+fn test_char_discriminants() {
+    assert_eq!(Command::Get.as_repr(), b'G');
+    assert_eq!(Command::Head.as_repr(), b'H');
+    assert_eq!(Command::Put.as_repr(), b'P');
+    assert_eq!(Command::Quit.as_repr(), b'Q');
+    assert_eq!(Command::Wait.as_repr(), b'R');
+
+    assert_eq!(Symbol::Alpha.as_repr(), 'α' as u16);
+    assert_eq!(Symbol::Beta.as_repr(), 'β' as u16);
+}
+
+#[test]
+fn test_as_char() {
+    assert_eq!(Command::Get.as_char(), 'G');
+    assert_eq!(Command::Head.as_char(), 'H');
+    assert_eq!(Command::Quit.as_char(), 'Q');
+
+    const PUT: char = Command::Put.as_char();
+    assert_eq!(PUT, 'P');
+}
+
+#[test]
+fn test_from_char() {
+    assert_eq!(Command::from_char('G'), Some(Command::Get));
+    assert_eq!(Command::from_char('R'), Some(Command::Wait));
+    assert_eq!(Command::from_char('g'), None);
+    // Non-ASCII characters never match, even if their low byte would
+    assert_eq!(Command::from_char('Ň'), None);
+
+    const HEAD: Option<Command> = Command::from_char('H');
+    assert_eq!(HEAD, Some(Command::Head));
+}
+
+#[test]
+fn test_try_from_char() {
+    assert_eq!(Command::try_from('P').unwrap(), Command::Put);
+
+    let err = Command::try_from('x').unwrap_err();
+    assert_eq!(err.0, 'x');
+    assert_eq!(err.to_string(), "unknown Command character: 'x'");
+}
+
+#[test]
+fn test_display_prints_character() {
+    assert_eq!(Command::Get.to_string(), "G");
+    assert_eq!(format!("[{:>3}]", Command::Quit), "[  Q]");
+
+    let line: String = [Command::Get, Command::Put, Command::Quit]
+        .iter()
+        .map(Command::to_string)
+        .collect();
+    assert_eq!(line, "GPQ");
+}
+
+#[test]
+fn test_integer_conversions_still_work() {
+    assert_eq!(Command::try_from(b'G').unwrap(), Command::Get);
+    assert_eq!(u8::from(Command::Wait), b'R');
+    assert!(Command::try_from(0u8).is_err());
+}
@@ -0,0 +1,84 @@
+use repr_cast::repr_cast;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8, atomic)]
+enum State {
+    Idle = 1,
+    Running = 2,
+    Stopped = 4,
+    #[repr_cast(skip)]
+    Poisoned = 0xFF,
+}
+
+#[derive(Debug, PartialEq)]
+#[repr_cast(i32, atomic)]
+enum Level {
+    Low = -1,
+    High = 1,
+}
+
+static GLOBAL: AtomicState = AtomicState::new(State::Idle);
+
+#[test]
+fn test_load_store_swap() {
+    let state = AtomicState::new(State::Idle);
+    assert_eq!(state.load(Ordering::Relaxed), State::Idle);
+    state.store(State::Running, Ordering::Release);
+    assert_eq!(state.load(Ordering::Acquire), State::Running);
+    assert_eq!(state.swap(State::Stopped, Ordering::AcqRel), State::Running);
+
+    // Skipped variants are never decoded by `from_repr` but can still be stored
+    state.store(State::Poisoned, Ordering::Relaxed);
+    assert_eq!(state.into_inner(), State::Poisoned);
+
+    assert_eq!(GLOBAL.load(Ordering::Relaxed), State::Idle);
+    assert_eq!(std::mem::size_of::<AtomicState>(), 1);
+}
+
+#[test]
+fn test_compare_exchange() {
+    let state = AtomicState::from(State::Idle);
+    assert_eq!(
+        state.compare_exchange(State::Idle, State::Running, Ordering::AcqRel, Ordering::Acquire),
+        Ok(State::Idle)
+    );
+    assert_eq!(
+        state.compare_exchange(State::Idle, State::Stopped, Ordering::AcqRel, Ordering::Acquire),
+        Err(State::Running)
+    );
+    while state
+        .compare_exchange_weak(State::Running, State::Idle, Ordering::AcqRel, Ordering::Relaxed)
+        .is_err()
+    {}
+    assert_eq!(format!("{:?}", state), "AtomicState(Idle)");
+
+    let level = AtomicLevel::new(Level::Low);
+    assert_eq!(
+        level.compare_exchange(Level::Low, Level::High, Ordering::SeqCst, Ordering::SeqCst),
+        Ok(Level::Low)
+    );
+    assert_eq!(level.into_inner(), Level::High);
+}
+
+#[test]
+fn test_fetch_update_across_threads() {
+    let state = Arc::new(AtomicState::new(State::Idle));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let state = Arc::clone(&state);
+            std::thread::spawn(move || {
+                state.fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| match current {
+                    State::Idle => Some(State::Running),
+                    _ => None,
+                })
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+    assert!(results.contains(&Err(State::Running)));
+    assert_eq!(state.load(Ordering::Relaxed), State::Running);
+}
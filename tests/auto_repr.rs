@@ -0,0 +1,52 @@
+use repr_cast::repr_cast;
+use std::convert::TryFrom;
+use std::mem::size_of;
+
+#[derive(Debug, PartialEq)]
+#[repr_cast(auto)]
+enum Small {
+    A,
+    B = 200,
+}
+
+#[derive(Debug, PartialEq)]
+#[repr_cast(auto)]
+pub enum Wide {
+    A = 255,
+    B, // 256
+}
+
+#[derive(Debug, PartialEq)]
+#[repr_cast(auto_signed)]
+enum Signed {
+    Low = -129,
+    High = 1 << 20,
+}
+
+#[derive(Debug, PartialEq)]
+#[repr_cast(auto, nonzero)]
+enum Byte {
+    One = 1,
+    Max = u8::MAX,
+}
+
+#[test]
+fn test_smallest_type() {
+    assert_eq!(size_of::<Small>(), 1);
+    assert_eq!(size_of::<Wide>(), 2);
+    assert_eq!(size_of::<Signed>(), 4);
+    assert_eq!(size_of::<Option<Byte>>(), 1);
+}
+
+#[test]
+fn test_repr_alias() {
+    let value: SmallRepr = Small::B.as_repr();
+    assert_eq!(value, 200u8);
+
+    let value: WideRepr = Wide::B.into();
+    assert_eq!(value, 256u16);
+
+    let value: SignedRepr = Signed::Low.as_repr();
+    assert_eq!(value, -129i32);
+    assert_eq!(Signed::try_from(1i32 << 20), Ok(Signed::High));
+}
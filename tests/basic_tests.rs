@@ -283,3 +283,29 @@ fn test_try_from_reference_implicit_discriminants() {
     // Test with invalid value
     assert!(ImplicitDiscriminant::try_from(&5u16).is_err());
 }
+
+/// Enums without `variants` keep the names of the variant lists free.
+#[repr_cast(u8)]
+#[derive(Debug, PartialEq)]
+enum Named {
+    One = 1,
+    Two = 2,
+}
+
+impl Named {
+    const COUNT: usize = 10;
+
+    fn name(&self) -> &'static str {
+        match self {
+            Named::One => "one",
+            Named::Two => "two",
+        }
+    }
+}
+
+#[test]
+fn test_own_variant_items() {
+    assert_eq!(Named::COUNT, 10);
+    assert_eq!(Named::Two.name(), "two");
+    assert_eq!(Named::from_repr(1), Some(Named::One));
+}
@@ -0,0 +1,124 @@
+use repr_cast::repr_cast;
+
+/// A message framed as a one-byte tag followed by its payload.
+#[repr_cast(u8, codec)]
+#[derive(Debug, PartialEq)]
+enum Message {
+    Ping = 1,
+    Data(u16, [u8; 3]) = 2,
+    Resize { width: u32, height: u32 } = 4,
+    Scale(f32),
+    Offset { buf: i64 },
+    Key { pressed: bool, key: char },
+}
+
+#[test]
+fn test_encode() {
+    let mut buf = Vec::new();
+    Message::Ping.encode(&mut buf);
+    assert_eq!(buf, [1]);
+
+    buf.clear();
+    Message::Data(0x0102, *b"abc").encode(&mut buf);
+    assert_eq!(buf, [2, 0x02, 0x01, b'a', b'b', b'c']);
+
+    buf.clear();
+    Message::Resize { width: 80, height: 24 }.encode(&mut buf);
+    assert_eq!(buf, [4, 80, 0, 0, 0, 24, 0, 0, 0]);
+}
+
+#[test]
+fn test_round_trip() {
+    let messages = [
+        Message::Ping,
+        Message::Data(7, [1, 2, 3]),
+        Message::Resize { width: 1920, height: 1080 },
+        Message::Scale(1.5),
+        Message::Offset { buf: -42 },
+        Message::Key { pressed: true, key: 'é' },
+    ];
+    let mut buf = Vec::new();
+    for message in &messages {
+        message.encode(&mut buf);
+    }
+
+    // Decoding one message after the other consumes the whole buffer
+    let mut rest = &buf[..];
+    for message in messages {
+        let (decoded, len) = Message::decode(rest).unwrap();
+        assert_eq!(decoded, message);
+        rest = &rest[len..];
+    }
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn test_decode_errors() {
+    assert_eq!(Message::decode(&[]), Err(MessageDecodeError::UnexpectedEnd));
+    assert_eq!(Message::decode(&[3, 0, 0]), Err(MessageDecodeError::UnknownTag(3)));
+    assert_eq!(Message::decode(&[4, 80, 0, 0, 0, 24]), Err(MessageDecodeError::UnexpectedEnd));
+    assert_eq!(MessageDecodeError::UnknownTag(3).to_string(), "unknown Message tag: 3");
+}
+
+#[test]
+fn test_bool_and_char_fields() {
+    let mut buf = Vec::new();
+    Message::Key { pressed: true, key: 'A' }.encode(&mut buf);
+    assert_eq!(buf, [7, 1, 0x41, 0, 0, 0]);
+
+    // A bool must be 0 or 1, and a char a Unicode scalar value
+    assert_eq!(Message::decode(&[7, 2, 0x41, 0, 0, 0]), Err(MessageDecodeError::InvalidValue));
+    assert_eq!(Message::decode(&[7, 0, 0x00, 0xD8, 0, 0]), Err(MessageDecodeError::InvalidValue));
+    assert_eq!(Message::decode(&[7, 0, 0x41, 0, 0]), Err(MessageDecodeError::UnexpectedEnd));
+    assert_eq!(MessageDecodeError::InvalidValue.to_string(), "invalid field value in Message bytes");
+}
+
+/// Fieldless enums encode just their tag, here in big-endian byte order.
+#[repr_cast(u16, codec(big_endian))]
+#[derive(Debug, PartialEq)]
+enum Opcode {
+    Nop = 0x0100,
+    Halt = 0x0200,
+}
+
+#[test]
+fn test_fieldless_big_endian() {
+    let mut buf = Vec::new();
+    Opcode::Halt.encode(&mut buf);
+    Opcode::Nop.encode(&mut buf);
+    assert_eq!(buf, [0x02, 0x00, 0x01, 0x00]);
+    assert_eq!(Opcode::decode(&buf), Ok((Opcode::Halt, 2)));
+    assert_eq!(Opcode::decode(&[0x01]), Err(OpcodeDecodeError::UnexpectedEnd));
+}
+
+/// Skipped variants are written by `encode` and read back by `decode`.
+#[repr_cast(u8, codec)]
+#[derive(Debug, PartialEq)]
+enum Record {
+    Current(u16) = 1,
+    #[repr_cast(skip)]
+    Legacy(u8) = 3,
+}
+
+#[repr_cast(u8, codec, mask = 0x0F)]
+#[derive(Debug, PartialEq)]
+enum Flagged {
+    Start = 1,
+    Stop = 2,
+}
+
+#[test]
+fn test_decode_skipped_variant() {
+    let mut buf = Vec::new();
+    Record::Legacy(9).encode(&mut buf);
+    assert_eq!(buf, [3, 9]);
+    assert_eq!(Record::decode(&buf), Ok((Record::Legacy(9), 2)));
+    assert_eq!(RecordTag::from_repr(3), None);
+}
+
+#[test]
+fn test_decode_masked_tag() {
+    assert_eq!(Flagged::decode(&[0x11]), Ok((Flagged::Start, 1)));
+    assert_eq!(Flagged::decode(&[0xF2]), Ok((Flagged::Stop, 1)));
+    assert_eq!(Flagged::decode(&[0x13]), Err(FlaggedDecodeError::UnknownTag(0x13)));
+}
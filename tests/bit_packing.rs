@@ -0,0 +1,65 @@
+use repr_cast::repr_cast;
+
+const HALT: u8 = 6;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8, bits = 3)]
+enum Mode {
+    Idle,
+    Run,
+    Sleep = 5,
+    Halt = HALT,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u16, bits = 5)]
+enum Channel {
+    First = 1,
+    Last = 31,
+}
+
+#[test]
+fn test_constants() {
+    assert_eq!(Mode::BITS, 3);
+    assert_eq!(Mode::MASK, 0b111);
+    assert_eq!(Channel::BITS, 5);
+    assert_eq!(Channel::MASK, 0b1_1111);
+}
+
+#[test]
+fn test_pack_into() {
+    assert_eq!(Mode::Sleep.pack_into(0, 0), 0b101);
+    assert_eq!(Mode::Run.pack_into(0, 4), 0b1_0000);
+
+    // Other bits are left untouched and the field is overwritten
+    let word = 0xFFFF_FFFF;
+    assert_eq!(Mode::Idle.pack_into(word, 8), 0xFFFF_F8FF);
+    assert_eq!(Mode::Halt.pack_into(word, 29), 0xDFFF_FFFF);
+
+    const PACKED: u32 = Channel::Last.pack_into(0, 27);
+    assert_eq!(PACKED, 31 << 27);
+}
+
+#[test]
+fn test_unpack_from() {
+    let word = Mode::Sleep.pack_into(Channel::Last.pack_into(0, 3), 0);
+    assert_eq!(Mode::unpack_from(word, 0), Ok(Mode::Sleep));
+    assert_eq!(Channel::unpack_from(word, 3), Ok(Channel::Last));
+
+    // 0b111 is not a variant
+    let err = Mode::unpack_from(0b111 << 10, 10).unwrap_err();
+    assert_eq!(err.0, 0b111);
+}
+
+#[test]
+#[should_panic(expected = "field does not fit in the word")]
+fn test_pack_into_out_of_range() {
+    Channel::First.pack_into(0, 28);
+}
+
+#[test]
+#[should_panic(expected = "field does not fit in the word")]
+fn test_unpack_from_huge_shift() {
+    // Large enough for `shift + BITS` to overflow
+    let _ = Mode::unpack_from(0, u32::MAX);
+}
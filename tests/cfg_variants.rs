@@ -0,0 +1,75 @@
+use repr_cast::repr_cast;
+
+// `cfg(test)` is enabled and `cfg(any())` never is, standing in for features
+// that are turned on and off
+#[derive(Debug, PartialEq)]
+#[repr_cast(u8, none = 0xFF, bits = 4, set)]
+enum Gated {
+    First,
+    #[cfg(any())]
+    Disabled,
+    Second,
+    #[cfg(all(test, not(miri)))]
+    #[repr_cast(alias = 9, rename = "third")]
+    Enabled,
+    #[cfg_attr(any(), cfg(all()), derive(Debug))]
+    #[cfg_attr(all(), cfg(any()))]
+    DisabledByCfgAttr = 7,
+    #[cfg(not(test))]
+    NotInTests,
+    #[cfg(test)]
+    InTests,
+}
+
+// No variant is enabled unconditionally
+#[derive(Debug, PartialEq)]
+#[repr_cast(u16)]
+enum AllGated {
+    #[cfg(any())]
+    Never,
+    #[cfg(test)]
+    Always = 5,
+    #[cfg(test)]
+    #[repr_cast(hidden)]
+    Hidden,
+}
+
+#[test]
+fn test_enabled_variants() {
+    assert_eq!(Gated::from_repr(0), Some(Gated::First));
+    assert_eq!(Gated::from_repr(1), Some(Gated::Second));
+    assert_eq!(Gated::from_repr(2), Some(Gated::Enabled));
+    assert_eq!(Gated::from_repr(9), Some(Gated::Enabled));
+    assert_eq!(Gated::from_repr(3), Some(Gated::InTests));
+    assert_eq!(Gated::Enabled.as_repr(), 2);
+    assert_eq!(Gated::Enabled.name(), "third");
+    assert_eq!(Gated::InTests.as_repr(), 3);
+}
+
+#[test]
+fn test_disabled_variants() {
+    // Implicit discriminants follow the variants that are enabled
+    assert_eq!(Gated::from_repr(7), None);
+    assert_eq!(Gated::from_repr(4), None);
+}
+
+#[test]
+fn test_all_variants_gated() {
+    assert_eq!(AllGated::from_repr(5), Some(AllGated::Always));
+    assert_eq!(AllGated::Hidden.as_repr(), 6);
+    assert_eq!(AllGated::COUNT, 1);
+    assert_eq!(AllGated::VARIANTS, &[AllGated::Always]);
+    assert_eq!(AllGated::Hidden.ordinal(), 1);
+}
+
+#[test]
+fn test_count_and_variants() {
+    assert_eq!(Gated::COUNT, 4);
+    assert_eq!(
+        Gated::VARIANTS,
+        &[Gated::First, Gated::Second, Gated::Enabled, Gated::InTests]
+    );
+    assert_eq!(Gated::InTests.ordinal(), 3);
+    assert_eq!(Gated::from_ordinal(2), Some(Gated::Enabled));
+    assert_eq!(GatedSet::all().bits(), 0b1111);
+}
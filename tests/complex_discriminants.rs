@@ -36,6 +36,19 @@ enum SignedMixed {
     Six,            // 6
 }
 
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8)]
+enum ConstFolded {
+    Shifted = 1 << 4,      // 16
+    AfterShift,            // 17
+    Ored = 0x20 | 0x01,    // 33
+    Scaled = (3 * 8),      // 24
+    Letter = b'A',         // 65
+    Lower = ('a' as u32 + 1) as u8, // 98
+    NearMax = u8::MAX - 1, // 254
+    Max,                   // 255
+}
+
 #[test]
 fn test_const_expressions() {
     // Test enum to int
@@ -138,3 +151,58 @@ fn test_discriminants_match_rust_behavior() {
     assert_eq!(WithConsts::Fourth.as_repr(), WithConsts::Fourth as u8);
     assert_eq!(WithConsts::Fifth.as_repr(), WithConsts::Fifth as u8);
 }
+
+#[test]
+fn test_const_folded_expressions() {
+    assert_eq!(ConstFolded::Shifted.as_repr(), 16);
+    assert_eq!(ConstFolded::AfterShift.as_repr(), 17);
+    assert_eq!(ConstFolded::Ored.as_repr(), 33);
+    assert_eq!(ConstFolded::Scaled.as_repr(), 24);
+    assert_eq!(ConstFolded::Letter.as_repr(), 65);
+    assert_eq!(ConstFolded::Lower.as_repr(), 98);
+    assert_eq!(ConstFolded::NearMax.as_repr(), 254);
+    assert_eq!(ConstFolded::Max.as_repr(), 255);
+
+    assert_eq!(ConstFolded::try_from(17).unwrap(), ConstFolded::AfterShift);
+    assert_eq!(ConstFolded::try_from(255).unwrap(), ConstFolded::Max);
+    assert!(ConstFolded::try_from(18).is_err());
+
+    // Unsuffixed literals take the type they are cast to
+    assert_eq!(CastLiterals::AllOnes.as_repr(), u32::MAX);
+    assert_eq!(CastLiterals::Next.as_repr(), 0x80);
+    assert_eq!(CastLiterals::try_from(0x80), Ok(CastLiterals::Next));
+}
+
+#[repr_cast(u32)]
+#[derive(Debug, PartialEq)]
+#[allow(clippy::unnecessary_cast)]
+enum CastLiterals {
+    AllOnes = 0xFFFF_FFFF as u32,
+    Low = 0x7F as u32,
+    Next,
+}
+
+#[repr_cast(i128)]
+#[derive(Debug, PartialEq)]
+enum SignedExtremes {
+    Min = i128::MIN,
+    Max = i128::MAX,
+}
+
+#[repr_cast(u128)]
+#[derive(Debug, PartialEq)]
+enum UnsignedExtremes {
+    Zero,
+    AboveSigned = i128::MAX as u128 + 1,
+    Max = u128::MAX,
+}
+
+#[test]
+fn test_largest_discriminants() {
+    assert_eq!(SignedExtremes::Max.as_repr(), i128::MAX);
+    assert_eq!(SignedExtremes::try_from(i128::MIN), Ok(SignedExtremes::Min));
+    assert_eq!(UnsignedExtremes::Max.as_repr(), u128::MAX);
+    assert_eq!(UnsignedExtremes::try_from(u128::MAX), Ok(UnsignedExtremes::Max));
+    assert!(UnsignedExtremes::try_from(1u128).is_err());
+    assert_eq!(UnsignedExtremes::AboveSigned.as_repr(), 1 << 127);
+}
@@ -0,0 +1,78 @@
+use repr_cast::repr_cast;
+
+#[repr_cast(u8)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Category {
+    Io = 1,
+    Net = 2,
+}
+
+#[repr_cast(u8)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Code {
+    Missing = 1,
+    Denied = 2,
+    Timeout = 3,
+}
+
+const TIMED_OUT: u16 = 0x0203;
+
+/// Error codes of the form `(category << 8) | code`.
+#[repr_cast(u16, compose(hi = Category, lo = Code))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum ErrorCode {
+    NotFound = (Io, Missing),
+    AccessDenied = (Category::Io, Code::Denied),
+    Unreachable = 0x0201,
+    TimedOut = TIMED_OUT,
+}
+
+#[test]
+fn test_composed_discriminants() {
+    assert_eq!(ErrorCode::NotFound.as_repr(), 0x0101);
+    assert_eq!(ErrorCode::AccessDenied.as_repr(), 0x0102);
+    assert_eq!(ErrorCode::try_from(0x0203u16), Ok(ErrorCode::TimedOut));
+}
+
+#[test]
+fn test_parts() {
+    assert_eq!(ErrorCode::NotFound.category(), Category::Io);
+    assert_eq!(ErrorCode::NotFound.code(), Code::Missing);
+    assert_eq!(ErrorCode::Unreachable.category(), Category::Net);
+    assert_eq!(ErrorCode::TimedOut.code(), Code::Timeout);
+
+    const DENIED: Option<ErrorCode> = ErrorCode::from_parts(Category::Io, Code::Denied);
+    assert_eq!(DENIED, Some(ErrorCode::AccessDenied));
+    assert_eq!(ErrorCode::from_parts(Category::Net, Code::Denied), None);
+}
+
+#[repr_cast(i8)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Severity {
+    Info = 0,
+    Fatal = -1,
+}
+
+#[repr_cast(u16)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Subsystem {
+    Disk = 3,
+    Wide = 0x103,
+}
+
+/// Parts that don't fit in their half don't produce another variant.
+#[repr_cast(i16, compose(hi = Severity, lo = Subsystem))]
+#[derive(Debug, PartialEq)]
+enum Event {
+    DiskInfo = (Info, Disk),
+    DiskFatal = (Fatal, Disk),
+}
+
+#[test]
+fn test_signed_and_wide_parts() {
+    assert_eq!(Event::DiskFatal.as_repr(), -253);
+    assert_eq!(Event::DiskFatal.severity(), Severity::Fatal);
+    assert_eq!(Event::DiskFatal.subsystem(), Subsystem::Disk);
+    assert_eq!(Event::from_parts(Severity::Info, Subsystem::Disk), Some(Event::DiskInfo));
+    assert_eq!(Event::from_parts(Severity::Info, Subsystem::Wide), None);
+}
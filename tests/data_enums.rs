@@ -0,0 +1,71 @@
+use repr_cast::repr_cast;
+
+/// A message with a payload depending on its type.
+#[repr_cast(u8, set)]
+#[derive(Debug, PartialEq, Clone)]
+enum Message {
+    Ping = 1,
+    /// Carries application data
+    Data(Vec<u8>) = 2,
+    Resize { width: u16, height: u16 } = 4,
+    Close(u16),
+}
+
+#[test]
+fn test_tag() {
+    assert_eq!(Message::Ping.tag(), 1);
+    assert_eq!(Message::Data(vec![1, 2]).tag(), 2);
+    assert_eq!(Message::Resize { width: 80, height: 24 }.tag(), 4);
+    assert_eq!(Message::Close(1000).tag(), 5);
+    assert_eq!(std::mem::discriminant(&Message::Close(0)), std::mem::discriminant(&Message::Close(1)));
+}
+
+#[test]
+fn test_tag_enum() {
+    let message = Message::Data(vec![0xAB]);
+    assert_eq!(MessageTag::from(&message), MessageTag::Data);
+    assert_eq!(message.kind(), MessageTag::Data);
+    assert_eq!(MessageTag::Close as u8, 5);
+
+    // The tag enum has the full set of conversions and options
+    assert_eq!(MessageTag::try_from(4u8), Ok(MessageTag::Resize));
+    assert_eq!(MessageTag::try_from(3u8), Err(MessageTagConversionError(3)));
+    assert_eq!(u8::from(MessageTag::Ping), 1);
+    assert_eq!(MessageTag::COUNT, 4);
+    assert_eq!(MessageTag::Resize.name(), "Resize");
+    assert!(MessageTagSet::all().contains(message.kind()));
+}
+
+#[test]
+fn test_dispatch_on_tag() {
+    let describe = |tag: u8| match MessageTag::from_repr(tag) {
+        Some(MessageTag::Ping) => "ping",
+        Some(MessageTag::Data) => "data",
+        Some(_) => "control",
+        None => "unknown",
+    };
+    assert_eq!(describe(Message::Ping.tag()), "ping");
+    assert_eq!(describe(Message::Data(vec![]).tag()), "data");
+    assert_eq!(describe(Message::Close(0).tag()), "control");
+    assert_eq!(describe(0), "unknown");
+}
+
+/// Generic enums keep their parameters; the tag enum has none.
+#[repr_cast(u16, variants)]
+enum Event<T> {
+    Started = 0x100,
+    Value(T),
+    #[cfg(any())]
+    Disabled(T),
+    Stopped = 0x200,
+}
+
+#[test]
+fn test_generic_enum() {
+    assert_eq!(Event::Value("x").tag(), 0x101);
+    assert_eq!(Event::<u8>::Started.tag(), 0x100);
+    assert_eq!(Event::<u8>::Stopped.tag(), 0x200);
+    assert_eq!(EventTag::from(&Event::Value(1.5)), EventTag::Value);
+    assert_eq!(EventTag::from_repr(0x100), Some(EventTag::Started));
+    assert_eq!(EventTag::VARIANTS, &[EventTag::Started, EventTag::Value, EventTag::Stopped]);
+}
@@ -0,0 +1,51 @@
+// Generated code must not trigger deprecation warnings for the variants
+#![deny(deprecated)]
+
+use repr_cast::repr_cast;
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq)]
+#[repr_cast(u8, also(u32), nonzero, none = 0xFF, bits = 7, ascii, reserved(0x00..0x20), variants)]
+enum Command {
+    Get = b'G',
+    #[deprecated(note = "use `Get`")]
+    Fetch = b'F',
+    Put = b'P',
+}
+
+#[derive(Debug, PartialEq)]
+#[repr_cast(u8, strict, mask(0x0F, try_from))]
+enum Mode {
+    Off = 0,
+    #[deprecated]
+    #[repr_cast(alias = 3)]
+    Legacy = 1,
+    On = 2,
+}
+
+#[test]
+fn test_is_deprecated() {
+    assert!(!Command::Get.is_deprecated());
+    assert!(!Command::Put.is_deprecated());
+    #[allow(deprecated)]
+    let fetch = Command::Fetch;
+    assert!(fetch.is_deprecated());
+}
+
+#[test]
+fn test_lenient_by_default() {
+    assert!(Command::from_repr(b'F').unwrap().is_deprecated());
+    assert!(Command::try_from(b'F').unwrap().is_deprecated());
+    assert!(Mode::from_repr(1).unwrap().is_deprecated());
+}
+
+#[test]
+fn test_strict() {
+    assert_eq!(Mode::from_repr_strict(2), Some(Mode::On));
+    assert_eq!(Mode::from_repr_strict(1), None);
+    assert_eq!(Mode::from_repr_strict(3), None);
+
+    assert_eq!(Mode::try_from(0xF2), Ok(Mode::On));
+    assert_eq!(Mode::try_from(0xF1).unwrap_err().0, 0xF1);
+    assert_eq!(Mode::try_from(&1).unwrap_err().0, 1);
+}
@@ -0,0 +1,54 @@
+use repr_cast::repr_cast;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8, map)]
+enum Status {
+    Pending = 10,
+    Active = 20,
+    #[repr_cast(hidden)]
+    Completed = 30,
+}
+
+#[test]
+fn test_index() {
+    let mut counters = StatusMap::<u32>::default();
+    counters[Status::Active] += 2;
+    counters[Status::Completed] += 1;
+    *counters.get_mut(&Status::Active) += 1;
+
+    assert_eq!(counters[Status::Pending], 0);
+    assert_eq!(*counters.get(&Status::Active), 3);
+    assert_eq!(counters.into_array(), [0, 3, 1]);
+    // Hidden variants are left out of `COUNT` but have an element
+    assert_eq!(Status::COUNT, 2);
+    assert_eq!(std::mem::size_of::<StatusMap<u32>>(), 12);
+}
+
+#[test]
+fn test_from_fn() {
+    let handlers = StatusMap::from_fn(|status| status.as_repr() * 2);
+    assert_eq!(handlers.as_array(), &[20, 40, 60]);
+
+    const NAMES: StatusMap<&str> = StatusMap::from_array(["pending", "active", "done"]);
+    assert_eq!(NAMES[Status::Completed], "done");
+    assert_eq!(*NAMES.get(&Status::Pending), "pending");
+}
+
+#[test]
+fn test_iteration() {
+    let mut map = StatusMap::from_fn(|status| status.ordinal());
+    for (status, value) in map.iter_mut() {
+        if status == Status::Active {
+            *value = 7;
+        }
+    }
+
+    let entries: Vec<_> = map.iter().map(|(status, value)| (status, *value)).collect();
+    assert_eq!(
+        entries,
+        [(Status::Pending, 0), (Status::Active, 7), (Status::Completed, 2)]
+    );
+    assert_eq!(map.values().sum::<usize>(), 9);
+    assert_eq!(map.clone(), map);
+    assert_eq!(format!("{:?}", map), "{Pending: 0, Active: 7, Completed: 2}");
+}
@@ -0,0 +1,65 @@
+use repr_cast::repr_cast;
+
+#[derive(Debug, PartialEq)]
+#[repr_cast(u16, set)]
+enum Opcode {
+    Nop = 0x100,
+    Load = 0x200,
+    Store = 0x300,
+    #[repr_cast(hidden)]
+    Halt = 0xFFF,
+}
+
+const SUPPORTED: OpcodeSet = OpcodeSet::empty().with(Opcode::Load).with(Opcode::Store);
+
+#[test]
+fn test_ordinals() {
+    assert_eq!(Opcode::Nop.ordinal(), 0);
+    assert_eq!(Opcode::Halt.ordinal(), 3);
+    assert_eq!(Opcode::from_ordinal(2), Some(Opcode::Store));
+    assert_eq!(Opcode::from_ordinal(4), None);
+}
+
+#[test]
+fn test_const_construction() {
+    assert_eq!(SUPPORTED.bits(), 0b0110);
+    assert_eq!(SUPPORTED.len(), 2);
+    assert!(SUPPORTED.contains(Opcode::Load));
+    assert!(!SUPPORTED.contains(Opcode::Nop));
+    assert_eq!(OpcodeSet::all().bits(), 0b1111);
+    assert_eq!(OpcodeSet::from_bits(0b0110), Some(SUPPORTED));
+    assert_eq!(OpcodeSet::from_bits(0b10000), None);
+    assert_eq!(OpcodeSet::from_bits_truncate(0xFF), OpcodeSet::all());
+    assert_eq!(std::mem::size_of::<OpcodeSet>(), 1);
+}
+
+#[test]
+fn test_set_algebra() {
+    let mut set = OpcodeSet::from(Opcode::Nop);
+    assert!(set.insert(Opcode::Load));
+    assert!(!set.insert(Opcode::Load));
+
+    assert_eq!(set | SUPPORTED, OpcodeSet::all().without(Opcode::Halt));
+    assert_eq!(set & SUPPORTED, OpcodeSet::from(Opcode::Load));
+    assert_eq!(set - SUPPORTED, OpcodeSet::from(Opcode::Nop));
+    assert_eq!(set ^ SUPPORTED, [Opcode::Nop, Opcode::Store].into_iter().collect());
+    assert_eq!(!set, SUPPORTED.without(Opcode::Load).with(Opcode::Halt));
+    assert!(OpcodeSet::from(Opcode::Load).is_subset(&SUPPORTED));
+    assert!(SUPPORTED.is_superset(&OpcodeSet::empty()));
+    assert!(set.difference(SUPPORTED).is_disjoint(&SUPPORTED));
+
+    assert!(set.remove(Opcode::Nop));
+    assert!(!set.remove(Opcode::Nop));
+    assert_eq!(set, OpcodeSet::from(Opcode::Load));
+}
+
+#[test]
+fn test_iteration() {
+    let set = OpcodeSet::all().without(Opcode::Load);
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        [Opcode::Nop, Opcode::Store, Opcode::Halt]
+    );
+    assert_eq!(format!("{:?}", set), "{Nop, Store, Halt}");
+    assert_eq!(format!("{:?}", OpcodeSet::empty()), "{}");
+}
@@ -0,0 +1,66 @@
+use repr_cast::repr_cast;
+
+const EXEC: u8 = 1 << 2;
+
+#[derive(Debug, PartialEq)]
+#[repr_cast(u8, flags)]
+enum Permission {
+    Read = 1,
+    Write = 2,
+    Exec = EXEC,
+    #[repr_cast(rename = "sticky")]
+    Sticky = 0x80,
+}
+
+#[test]
+fn test_bits_roundtrip() {
+    let flags = Permission::Read | Permission::Exec;
+    assert_eq!(flags.bits(), 0b101);
+    assert_eq!(PermissionFlags::from_bits(0b101), Some(flags));
+    assert_eq!(PermissionFlags::from_bits(0b1000), None);
+    assert_eq!(PermissionFlags::from_bits_truncate(0b1101), flags);
+    assert_eq!(PermissionFlags::all().bits(), 0x87);
+    assert!(PermissionFlags::empty().is_empty());
+    assert!(PermissionFlags::from_bits_truncate(0xFF).is_all());
+
+    const READ_ONLY: Option<PermissionFlags> = PermissionFlags::from_bits(1);
+    assert_eq!(READ_ONLY, Some(PermissionFlags::from(Permission::Read)));
+}
+
+#[test]
+fn test_operations() {
+    let mut flags = PermissionFlags::from(Permission::Read);
+    flags.insert(Permission::Write);
+    assert!(flags.contains(Permission::Write));
+    assert!(flags.contains(Permission::Read | Permission::Write));
+    assert!(!flags.contains(Permission::Read | Permission::Exec));
+
+    flags.remove(Permission::Read);
+    assert!(!flags.contains(Permission::Read));
+    assert_eq!(flags, PermissionFlags::from(Permission::Write));
+
+    assert_eq!(!flags, Permission::Read | Permission::Exec | Permission::Sticky);
+    assert_eq!(flags & PermissionFlags::all(), flags);
+    assert_eq!(flags | Permission::Exec, Permission::Write | Permission::Exec);
+
+    flags |= PermissionFlags::from(Permission::Sticky);
+    flags &= !PermissionFlags::from(Permission::Write);
+    assert_eq!(flags.bits(), 0x80);
+}
+
+#[test]
+fn test_iteration() {
+    let flags = Permission::Exec | Permission::Read;
+    let set: Vec<_> = flags.iter().collect();
+    assert_eq!(set, [Permission::Read, Permission::Exec]);
+
+    let collected: PermissionFlags = set.into_iter().collect();
+    assert_eq!(collected, flags);
+
+    assert_eq!(format!("{:?}", flags), "PermissionFlags(Read | Exec)");
+    assert_eq!(
+        format!("{:?}", flags | Permission::Sticky),
+        "PermissionFlags(Read | Exec | sticky)"
+    );
+    assert_eq!(format!("{:?}", PermissionFlags::empty()), "PermissionFlags()");
+}
@@ -0,0 +1,46 @@
+use repr_cast::repr_cast;
+use std::convert::TryFrom;
+
+const CLOSED: u8 = 0x03;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8, mask = 0x0F)]
+enum PortState {
+    Off = 0x00,
+    Listening = 0x01,
+    Open = 0x02,
+    Closed = CLOSED,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u16, mask(0x00FF, try_from))]
+enum Register {
+    Control = 0x10,
+    Status = 0x11,
+}
+
+#[test]
+fn test_from_repr_masked() {
+    assert_eq!(PortState::DECODE_MASK, 0x0F);
+    assert_eq!(PortState::from_repr_masked(0xA1), Some(PortState::Listening));
+    assert_eq!(PortState::from_repr_masked(0xF3), Some(PortState::Closed));
+    assert_eq!(PortState::from_repr_masked(0x0E), None);
+
+    const OPEN: Option<PortState> = PortState::from_repr_masked(0x72);
+    assert_eq!(OPEN, Some(PortState::Open));
+}
+
+#[test]
+fn test_try_from_is_strict_by_default() {
+    assert_eq!(PortState::try_from(0x01), Ok(PortState::Listening));
+    assert_eq!(PortState::try_from(0xA1).unwrap_err().0, 0xA1);
+}
+
+#[test]
+fn test_try_from_masked() {
+    assert_eq!(Register::try_from(0xAB10), Ok(Register::Control));
+    assert_eq!(Register::try_from(&0x0011), Ok(Register::Status));
+
+    // The error carries the original, unmasked value
+    assert_eq!(Register::try_from(0xAB12).unwrap_err().0, 0xAB12);
+}
@@ -0,0 +1,71 @@
+use repr_cast::repr_cast;
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Opcode(pub u16);
+
+mod wire {
+    /// A newtype whose field is private to this module.
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    pub struct Tag(u8);
+
+    impl Tag {
+        pub fn from_raw(raw: u8) -> Self {
+            Tag(raw)
+        }
+
+        pub fn raw(&self) -> u8 {
+            self.0
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u16, newtype = Opcode)]
+enum Instruction {
+    Load = 0x10,
+    Store = 0x11,
+    Jump = 0x20,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8, newtype(wire::Tag, new = from_raw, get = raw))]
+enum Kind {
+    Request = 1,
+    Response = 2,
+}
+
+#[test]
+fn test_enum_to_newtype() {
+    assert_eq!(Opcode::from(Instruction::Load), Opcode(0x10));
+    assert_eq!(Opcode::from(&Instruction::Jump), Opcode(0x20));
+
+    let opcode: Opcode = Instruction::Store.into();
+    assert_eq!(opcode, Opcode(0x11));
+}
+
+#[test]
+fn test_newtype_to_enum() {
+    assert_eq!(Instruction::try_from(Opcode(0x20)).unwrap(), Instruction::Jump);
+
+    let err = Instruction::try_from(Opcode(0x99)).unwrap_err();
+    assert_eq!(err.0, 0x99);
+}
+
+#[test]
+fn test_private_field_newtype() {
+    let tag = wire::Tag::from(Kind::Response);
+    assert_eq!(tag.raw(), 2);
+    assert_eq!(wire::Tag::from(&Kind::Request), wire::Tag::from_raw(1));
+
+    assert_eq!(Kind::try_from(wire::Tag::from_raw(1)).unwrap(), Kind::Request);
+    assert!(Kind::try_from(wire::Tag::from_raw(3)).is_err());
+}
+
+#[test]
+fn test_round_trip_through_newtype() {
+    for instruction in [Instruction::Load, Instruction::Store, Instruction::Jump] {
+        let opcode = Opcode::from(instruction);
+        assert_eq!(Instruction::try_from(opcode).unwrap(), instruction);
+    }
+}
@@ -0,0 +1,81 @@
+use repr_cast::repr_cast;
+use std::convert::TryFrom;
+use std::mem::size_of;
+use std::num::{NonZeroI16, NonZeroU8};
+
+const LATE: u8 = 9;
+
+// Detected: every discriminant is known and none is zero
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8)]
+enum Field {
+    Name = 1,
+    Address,
+    Phone = 5,
+}
+
+// Enforced: `Late` can't be evaluated by the macro and is checked at compile time
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8, nonzero)]
+enum Schedule {
+    Early = 1,
+    Late = LATE,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(i16)]
+enum Offset {
+    Back = -1,
+    Forward = 1,
+}
+
+// Opted out: the enum converts to `NonZeroU8` its own way
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8, nonzero = false)]
+enum Priority {
+    Low = 1,
+    High = 2,
+}
+
+impl From<Priority> for NonZeroU8 {
+    fn from(priority: Priority) -> Self {
+        NonZeroU8::new(priority.as_repr() * 10).unwrap()
+    }
+}
+
+#[test]
+fn test_opt_out() {
+    assert_eq!(NonZeroU8::from(Priority::High).get(), 20);
+    assert_eq!(Priority::try_from(1), Ok(Priority::Low));
+}
+
+#[test]
+fn test_enum_to_nonzero() {
+    assert_eq!(NonZeroU8::from(Field::Name).get(), 1);
+    assert_eq!(NonZeroU8::from(&Field::Address).get(), 2);
+    assert_eq!(NonZeroU8::from(Schedule::Late).get(), LATE);
+    assert_eq!(NonZeroI16::from(Offset::Back).get(), -1);
+
+    const PHONE: NonZeroU8 = Field::Phone.as_nonzero();
+    assert_eq!(PHONE.get(), 5);
+}
+
+#[test]
+fn test_nonzero_to_enum() {
+    let five = NonZeroU8::new(5).unwrap();
+    assert_eq!(Field::try_from(five).unwrap(), Field::Phone);
+    assert_eq!(Field::try_from(&five).unwrap(), Field::Phone);
+
+    let three = NonZeroU8::new(3).unwrap();
+    assert_eq!(Field::try_from(three).unwrap_err().0, 3);
+
+    let nine = NonZeroU8::new(9).unwrap();
+    assert_eq!(Schedule::try_from(nine).unwrap(), Schedule::Late);
+}
+
+#[test]
+fn test_option_has_repr_size() {
+    assert_eq!(size_of::<Option<Field>>(), size_of::<u8>());
+    assert_eq!(size_of::<Option<Schedule>>(), size_of::<u8>());
+    assert_eq!(size_of::<Option<Offset>>(), size_of::<i16>());
+}
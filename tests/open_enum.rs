@@ -0,0 +1,60 @@
+use repr_cast::repr_cast;
+
+/// A message type, including types added by newer protocol versions.
+// Derives after the attribute apply to `MessageTypeKnown`
+#[repr_cast(u16, open, set)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum MessageType {
+    /// Opens a session
+    Hello = 1,
+    Data = 2,
+    #[deprecated]
+    Legacy = 3,
+    Goodbye = 0xFF,
+}
+
+#[test]
+fn test_known_values() {
+    let message = MessageType::from(2u16);
+    assert_eq!(message, MessageType::Data);
+    assert_eq!(message.known(), Some(MessageTypeKnown::Data));
+    assert!(message.is_known());
+    assert_eq!(message.get(), 2);
+    assert_eq!(MessageType::from(MessageTypeKnown::Goodbye), MessageType::Goodbye);
+    assert_eq!(MessageTypeKnown::try_from(MessageType::Hello), Ok(MessageTypeKnown::Hello));
+    assert_eq!(std::mem::size_of::<MessageType>(), 2);
+
+    // Options apply to the known enum
+    assert!(MessageTypeKnownSet::all().contains(MessageTypeKnown::Goodbye));
+}
+
+#[test]
+fn test_unknown_values_round_trip() {
+    let message = MessageType::new(0x1234);
+    assert_eq!(message.known(), None);
+    assert_eq!(u16::from(message), 0x1234);
+    assert_eq!(
+        MessageTypeKnown::try_from(message),
+        Err(MessageTypeKnownConversionError(0x1234))
+    );
+}
+
+#[test]
+fn test_formatting() {
+    assert_eq!(format!("{:?}", MessageType::Hello), "Hello");
+    assert_eq!(MessageType::Goodbye.to_string(), "Goodbye");
+    assert_eq!(format!("{:?}", MessageType(7)), "Unknown(7)");
+    assert_eq!(MessageType(7).to_string(), "Unknown(7)");
+}
+
+#[test]
+fn test_pattern_matching() {
+    let describe = |message: MessageType| match message {
+        MessageType::Hello => "hello",
+        MessageType::Goodbye => "goodbye",
+        _ => "other",
+    };
+    assert_eq!(describe(MessageType::from(1u16)), "hello");
+    assert_eq!(describe(MessageType(0xFF)), "goodbye");
+    assert_eq!(describe(MessageType(0x100)), "other");
+}
@@ -0,0 +1,48 @@
+use repr_cast::repr_cast;
+
+const PAUSED: u8 = 7;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8, none = 0xFF)]
+enum Status {
+    Pending,
+    Active,
+    Paused = PAUSED,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(i16, none = i16::MIN)]
+enum Temperature {
+    Freezing = -10,
+    Mild = 15,
+}
+
+#[test]
+fn test_encode_option() {
+    assert_eq!(Status::encode_option(Some(Status::Active)), 1);
+    assert_eq!(Status::encode_option(Some(Status::Paused)), PAUSED);
+    assert_eq!(Status::encode_option(None), 0xFF);
+    assert_eq!(Temperature::encode_option(None), i16::MIN);
+
+    const ENCODED: u8 = Status::encode_option(None);
+    assert_eq!(ENCODED, Status::NONE_SENTINEL);
+}
+
+#[test]
+fn test_decode_option() {
+    assert_eq!(Status::decode_option(0), Ok(Some(Status::Pending)));
+    assert_eq!(Status::decode_option(0xFF), Ok(None));
+    assert_eq!(Temperature::decode_option(-10), Ok(Some(Temperature::Freezing)));
+    assert_eq!(Temperature::decode_option(i16::MIN), Ok(None));
+
+    let err = Status::decode_option(3).unwrap_err();
+    assert_eq!(err.0, 3);
+}
+
+#[test]
+fn test_option_round_trip() {
+    for value in [None, Some(Status::Pending), Some(Status::Active), Some(Status::Paused)] {
+        let encoded = Status::encode_option(value);
+        assert_eq!(Status::decode_option(encoded), Ok(value));
+    }
+}
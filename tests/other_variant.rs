@@ -0,0 +1,92 @@
+use repr_cast::repr_cast;
+
+const BASE: u8 = 0x40;
+
+// Derives after the attribute see the `Unknown` variant
+#[repr_cast(u8, other = Unknown, also(u16), none = 0xFF, atomic)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Command {
+    Read = 1,
+    Write,
+    Erase = BASE,
+    Lock,
+    #[deprecated]
+    Reset = 0x80,
+}
+
+#[repr_cast(u16, other = Vendor, also(u32), mask(0x0FFF, try_from))]
+#[derive(Debug, PartialEq)]
+enum Register {
+    Status = 0x001,
+    Control = 0x002,
+}
+
+// `Unknown` takes the first free value when the one after `High` doesn't fit
+#[repr_cast(i8, other = Unknown)]
+#[derive(Debug, PartialEq)]
+enum Level {
+    Low = -128,
+    Middle = -127,
+    High = 127,
+}
+
+#[test]
+fn test_from_is_infallible() {
+    assert_eq!(Command::from(1), Command::Read);
+    assert_eq!(Command::from(2), Command::Write);
+    assert_eq!(Command::from(&0x41), Command::Lock);
+    assert_eq!(Command::from(0x33), Command::Unknown(0x33));
+    assert_eq!(Command::from_repr(0x33), None);
+
+    let command: Command = 0x40.into();
+    assert_eq!(command, Command::Erase);
+}
+
+#[test]
+fn test_as_repr_round_trip() {
+    for value in 0..=u8::MAX {
+        assert_eq!(Command::from(value).as_repr(), value);
+        assert_eq!(u8::from(Command::from(value)), value);
+    }
+    assert_eq!(Command::Lock.as_repr(), 0x41);
+}
+
+#[test]
+fn test_layout() {
+    // The enum keeps its `#[repr(u8)]` and discriminants, with the tag first
+    let tag = |command: &Command| unsafe { *(command as *const Command as *const u8) };
+    assert_eq!(tag(&Command::Read), 1);
+    assert_eq!(tag(&Command::Write), 2);
+    assert_eq!(tag(&Command::Erase), 0x40);
+    assert_eq!(tag(&Command::Unknown(0x80)), 0x81);
+    assert_eq!(std::mem::size_of::<Command>(), 2);
+
+    assert_eq!(Level::from(-127), Level::Middle);
+    assert_eq!(Level::from(-126), Level::Unknown(-126));
+    assert_eq!(unsafe { *(&Level::Unknown(0) as *const Level as *const i8) }, -126);
+}
+
+#[test]
+fn test_variant_info() {
+    assert_eq!(Command::COUNT, 5);
+    assert_eq!(Command::VARIANTS.len(), 5);
+    assert_eq!(Command::Unknown(9).name(), "Unknown");
+    assert_eq!(Command::Unknown(9).ordinal(), Command::COUNT);
+    assert!(!Command::Unknown(0x80).is_deprecated());
+}
+
+#[test]
+fn test_with_other_options() {
+    assert_eq!(Command::try_from(0x141u16), Err(CommandIntConversionError(0x141)));
+    assert_eq!(Command::try_from(0x33u16), Ok(Command::Unknown(0x33)));
+    assert_eq!(u16::from(Command::Unknown(7)), 7);
+    assert_eq!(Command::decode_option(0x33), Ok(Some(Command::Unknown(0x33))));
+    assert_eq!(Command::decode_option(0xFF), Ok(None));
+
+    assert_eq!(Register::from(0xF002), Register::Control);
+    assert_eq!(Register::from(0xF150), Register::Vendor(0xF150));
+    assert_eq!(Register::try_from(0x10002u32), Err(RegisterIntConversionError(0x10002)));
+
+    let atomic = AtomicCommand::new(Command::Unknown(0x22));
+    assert_eq!(atomic.load(std::sync::atomic::Ordering::Relaxed), Command::Unknown(0x22));
+}
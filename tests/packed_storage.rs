@@ -0,0 +1,89 @@
+use repr_cast::repr_cast;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8, packed)]
+enum Cell {
+    Empty = b'.',
+    Wall = b'#',
+    Water = b'~',
+    Sand = b's',
+    Grass = b'g',
+}
+
+#[derive(Debug, PartialEq)]
+#[repr_cast(u8, packed)]
+enum Bit {
+    Off,
+    On,
+}
+
+const GRID_WORDS: usize = CellPackedVec::words_for(100);
+
+#[test]
+fn test_element_width() {
+    assert_eq!(CellPackedVec::ELEMENT_BITS, 3);
+    assert_eq!(CellPackedVec::PER_WORD, 21);
+    assert_eq!(BitPackedVec::ELEMENT_BITS, 1);
+    assert_eq!(BitPackedVec::words_for(65), 2);
+}
+
+#[test]
+fn test_vec() {
+    let mut cells = CellPackedVec::new();
+    assert!(cells.is_empty());
+    for i in 0..50 {
+        cells.push(Cell::from_ordinal(i % Cell::COUNT).unwrap());
+    }
+    assert_eq!(cells.len(), 50);
+    assert_eq!(cells.get(21), Some(Cell::Wall));
+    assert_eq!(cells.get(50), None);
+
+    cells.set(21, Cell::Grass);
+    assert_eq!(cells.get(21), Some(Cell::Grass));
+    assert_eq!(cells.get(22), Some(Cell::Water));
+    assert_eq!(cells.iter().filter(|cell| *cell == Cell::Grass).count(), 11);
+
+    assert_eq!(cells.pop(), Some(Cell::Grass));
+    assert_eq!(cells.len(), 49);
+    cells.clear();
+    assert_eq!(cells.pop(), None);
+}
+
+#[test]
+fn test_vec_conversions() {
+    let values = vec![Cell::Wall, Cell::Sand, Cell::Empty];
+    let mut packed = CellPackedVec::from(values.clone());
+    assert_eq!(Vec::from(packed.clone()), values);
+    assert_eq!(format!("{:?}", packed), "[Wall, Sand, Empty]");
+
+    // Popped elements leave no trace in the storage
+    packed.push(Cell::Grass);
+    packed.pop();
+    assert_eq!(packed, values.into_iter().collect());
+
+    let bits: BitPackedVec = [Bit::On, Bit::Off, Bit::On].into_iter().collect();
+    assert_eq!(bits.iter().collect::<Vec<_>>(), [Bit::On, Bit::Off, Bit::On]);
+}
+
+#[test]
+fn test_array() {
+    const GRID: CellPackedArray<GRID_WORDS> = CellPackedArray::new(Cell::Water);
+    let mut grid = GRID;
+    assert_eq!(grid.len(), 105);
+    assert_eq!(std::mem::size_of_val(&grid), 40);
+    assert_eq!(grid.get(104), Some(Cell::Water));
+    assert_eq!(grid.get(105), None);
+
+    grid.set(42, Cell::Wall);
+    assert_eq!(grid.get(42), Some(Cell::Wall));
+    assert_eq!(grid.get(41), Some(Cell::Water));
+    assert_eq!(grid.iter().filter(|cell| *cell == Cell::Wall).count(), 1);
+    assert_ne!(grid, GRID);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_set_out_of_bounds() {
+    let mut cells = CellPackedVec::from(vec![Cell::Wall]);
+    cells.set(1, Cell::Sand);
+}
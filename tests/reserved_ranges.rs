@@ -0,0 +1,62 @@
+use repr_cast::repr_cast;
+use std::convert::TryFrom;
+
+const VENDOR_START: u8 = 0xF0;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u8, reserved(0x10..=0x1F, VENDOR_START..0xFF), also(u16), none = 0xFF)]
+enum Frame {
+    Data = 0x00,
+    Ack = 0x01,
+    Nack = 0x02,
+    Ping = 0x20,
+}
+
+#[test]
+fn test_is_reserved() {
+    assert!(Frame::is_reserved(0x10));
+    assert!(Frame::is_reserved(0x1F));
+    assert!(Frame::is_reserved(0xF0));
+    assert!(Frame::is_reserved(0xFE));
+    assert!(!Frame::is_reserved(0xFF));
+    assert!(!Frame::is_reserved(0x03));
+
+    const RESERVED: [bool; 2] = [Frame::is_reserved(0x15), Frame::is_reserved(0x21)];
+    assert_eq!(RESERVED, [true, false]);
+}
+
+#[test]
+fn test_error_kinds() {
+    assert_eq!(Frame::try_from(0x01u8), Ok(Frame::Ack));
+    assert_eq!(Frame::try_from(0x12u8), Err(FrameConversionError::Reserved(0x12)));
+    assert_eq!(Frame::try_from(&0x03u8), Err(FrameConversionError::Unknown(0x03)));
+    assert_eq!(Frame::try_from(0xF5u8).unwrap_err().value(), 0xF5);
+
+    assert_eq!(Frame::try_from(0x12u8).unwrap_err().to_string(), "reserved Frame value: 18");
+    assert_eq!(Frame::try_from(0x03u8).unwrap_err().to_string(), "unknown Frame variant: 3");
+}
+
+#[test]
+fn test_error_kinds_in_other_conversions() {
+    assert_eq!(Frame::try_from(0x0012u16), Err(FrameIntConversionError::Reserved(0x0012u16)));
+    assert_eq!(Frame::try_from(0x0112u16), Err(FrameIntConversionError::Unknown(0x0112u16)));
+
+    assert_eq!(Frame::decode_option(0xFF), Ok(None));
+    assert_eq!(Frame::decode_option(0xF1), Err(FrameConversionError::Reserved(0xF1)));
+}
+
+#[repr_cast(u128, reserved(0x10..))]
+#[derive(Debug, PartialEq)]
+enum WideStatus {
+    Ready = 1,
+    Busy = 2,
+}
+
+#[test]
+fn test_open_range_of_128_bit_type() {
+    assert!(WideStatus::is_reserved(0x10));
+    assert!(WideStatus::is_reserved(u128::MAX));
+    assert!(!WideStatus::is_reserved(3));
+    assert_eq!(WideStatus::try_from(u128::MAX), Err(WideStatusConversionError::Reserved(u128::MAX)));
+    assert_eq!(WideStatus::try_from(3u128), Err(WideStatusConversionError::Unknown(3)));
+}
@@ -0,0 +1,10 @@
+use repr_cast::repr_cast;
+
+// This should fail because the repr type cannot be listed in `also`
+#[repr_cast(u8, also(u16, u8))]
+enum Status {
+    Pending,
+    Active,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use repr_cast::repr_cast;
+
+const HIGH: u8 = 0xC8;
+
+// This should fail because a const discriminant is checked at compile time
+#[repr_cast(u8, ascii)]
+enum Command {
+    Get = 'G',
+    High = HIGH,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use repr_cast::repr_cast;
+
+// This should fail because `ascii` enums must only have ASCII discriminants
+#[repr_cast(u8, ascii)]
+enum Command {
+    Get = 'G',
+    Escape = 0x9B,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use repr_cast::repr_cast;
+
+// This should fail because `auto` picks an unsigned type
+#[repr_cast(auto)]
+enum Level {
+    Low = -1,
+    High = 1,
+}
+
+fn main() {}
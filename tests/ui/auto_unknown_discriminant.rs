@@ -0,0 +1,12 @@
+use repr_cast::repr_cast;
+
+const BASE: u8 = 10;
+
+// This should fail because `auto` can't size a discriminant given by a const
+#[repr_cast(auto)]
+enum Level {
+    Low = BASE,
+    High,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use repr_cast::repr_cast;
+
+const WIDE: u8 = 9;
+
+// This should fail because `Wide` does not fit in 3 bits
+#[repr_cast(u8, bits = 3)]
+enum Mode {
+    Idle,
+    Wide = WIDE,
+}
+
+fn main() {}
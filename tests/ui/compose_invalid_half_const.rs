@@ -0,0 +1,22 @@
+use repr_cast::repr_cast;
+
+#[repr_cast(u8)]
+enum Category {
+    Io = 1,
+}
+
+#[repr_cast(u8)]
+enum Code {
+    Missing = 1,
+}
+
+const NET_MISSING: u16 = 0x0201;
+
+// The high half of `Unreachable` is not a `Category`
+#[repr_cast(u16, compose(hi = Category, lo = Code))]
+enum ErrorCode {
+    NotFound = (Io, Missing),
+    Unreachable = NET_MISSING,
+}
+
+fn main() {}
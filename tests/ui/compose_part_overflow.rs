@@ -0,0 +1,22 @@
+use repr_cast::repr_cast;
+
+#[repr_cast(u8)]
+enum Category {
+    Io = 1,
+}
+
+#[repr_cast(u16)]
+enum Code {
+    Fine = 0,
+    Missing = 0xFF,
+    Big = 0x1FF,
+}
+
+// `Code::Big` doesn't fit in the low 8 bits and would turn into `Missing`
+#[repr_cast(u16, compose(hi = Category, lo = Code))]
+enum ErrorCode {
+    Success = (Io, Fine),
+    Overflowing = (Io, Big),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use repr_cast::repr_cast;
+
+// This should fail because the discriminant does not fit in u8
+#[repr_cast(u8)]
+enum Overflowing {
+    Small = 1,
+    Big = 200 + 100,
+}
+
+fn main() {}
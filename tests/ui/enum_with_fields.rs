@@ -1,6 +1,7 @@
 use repr_cast::repr_cast;
 
-#[repr_cast(u8)]
+// The catch-all variant can't be added to an enum with fields
+#[repr_cast(u8, other = Unknown)]
 enum EnumWithFields {
     Variant1(u32),
     Variant2,
@@ -1,6 +1,7 @@
 use repr_cast::repr_cast;
 
-#[repr_cast(u8)]
+// An open enum is a newtype around the repr type, which can't hold fields
+#[repr_cast(u8, open)]
 enum EnumWithNamedFields {
     Variant1 { x: u32, y: u32 },
     Variant2,
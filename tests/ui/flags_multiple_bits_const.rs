@@ -0,0 +1,13 @@
+use repr_cast::repr_cast;
+
+const READ_WRITE: u8 = 0b11;
+
+// This should fail because `ReadWrite` is not a single bit
+#[repr_cast(u8, flags)]
+enum Permission {
+    Read = 1,
+    Write = 2,
+    ReadWrite = READ_WRITE,
+}
+
+fn main() {}
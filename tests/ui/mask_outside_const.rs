@@ -0,0 +1,12 @@
+use repr_cast::repr_cast;
+
+const WIDE: u8 = 0x13;
+
+// This should fail because `Wide` has bits outside of the mask
+#[repr_cast(u8, mask = 0x0F)]
+enum PortState {
+    Off,
+    Wide = WIDE,
+}
+
+fn main() {}
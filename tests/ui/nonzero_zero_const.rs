@@ -0,0 +1,12 @@
+use repr_cast::repr_cast;
+
+const NONE: u8 = 0;
+
+// This should fail because `nonzero` enums cannot have a zero discriminant
+#[repr_cast(u8, nonzero)]
+enum Presence {
+    Absent = NONE,
+    Present = 1,
+}
+
+fn main() {}
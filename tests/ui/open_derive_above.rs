@@ -0,0 +1,11 @@
+use repr_cast::repr_cast;
+
+// Derives above the attribute apply to the newtype, which derives them itself
+#[derive(Debug, PartialEq)]
+#[repr_cast(u16, open)]
+enum MessageType {
+    Hello = 1,
+    Goodbye = 2,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use repr_cast::repr_cast;
+
+// Derives above the attribute don't see the `Unknown` variant it adds
+#[derive(Debug)]
+#[repr_cast(u8, other = Unknown)]
+enum Command {
+    Read = 1,
+    Write = 2,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use repr_cast::repr_cast;
+
+const LEGACY_OK: u8 = 0x12;
+
+// This should fail because the alias of `Ok` is inside the range of `Busy`
+#[repr_cast(u8)]
+enum Reply {
+    #[repr_cast(alias = LEGACY_OK)]
+    Ok = 0,
+    #[repr_cast(range = 0x10..=0x1F)]
+    Busy = 0x10,
+}
+
+fn main() {}
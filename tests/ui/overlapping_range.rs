@@ -0,0 +1,12 @@
+use repr_cast::repr_cast;
+
+// This should fail because the ranges of `Success` and `Redirect` overlap
+#[repr_cast(u16)]
+enum Reply {
+    #[repr_cast(range = 200..=299)]
+    Success = 200,
+    #[repr_cast(range = 250..400)]
+    Redirect = 300,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use repr_cast::repr_cast;
+
+const PING: u8 = 0x12;
+
+// This should fail because `Ping` falls in the reserved range
+#[repr_cast(u8, reserved(0x10..=0x1F))]
+enum Frame {
+    Data,
+    Ping = PING,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use repr_cast::repr_cast;
+
+const LAST: u8 = 0xFF;
+
+// This should fail because the `none` sentinel is also a discriminant
+#[repr_cast(u8, none = 0xFF)]
+enum Status {
+    Pending,
+    Last = LAST,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use repr_cast::repr_cast;
+
+// This should fail because `bogus` is not a repr_cast option
+#[repr_cast(u8, bogus)]
+enum Status {
+    Pending,
+    Active,
+}
+
+fn main() {}
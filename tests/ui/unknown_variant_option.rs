@@ -0,0 +1,11 @@
+use repr_cast::repr_cast;
+
+// This should fail because `frobnicate` is not a variant option
+#[repr_cast(u8)]
+enum Status {
+    Pending,
+    #[repr_cast(frobnicate)]
+    Active,
+}
+
+fn main() {}
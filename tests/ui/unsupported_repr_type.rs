@@ -0,0 +1,10 @@
+use repr_cast::repr_cast;
+
+// This should fail because the repr type is not a primitive integer
+#[repr_cast(C)]
+enum NotAnInteger {
+    A,
+    B,
+}
+
+fn main() {}
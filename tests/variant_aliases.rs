@@ -0,0 +1,44 @@
+use repr_cast::repr_cast;
+use std::convert::TryFrom;
+
+const LEGACY_ERROR: u16 = 9;
+const CLIENT_ERROR_START: u16 = 400;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr_cast(u16)]
+enum Reply {
+    #[repr_cast(alias = 0)]
+    Ok = 1,
+    #[repr_cast(range = 200..=299)]
+    Success = 200,
+    #[repr_cast(alias = 2, alias = 3)]
+    #[repr_cast(range = 300..400)]
+    Redirect = 300,
+    #[repr_cast(alias = LEGACY_ERROR, range = CLIENT_ERROR_START..)]
+    Failure = 500,
+}
+
+#[test]
+fn test_aliases() {
+    assert_eq!(Reply::from_repr(0), Some(Reply::Ok));
+    assert_eq!(Reply::from_repr(1), Some(Reply::Ok));
+    assert_eq!(Reply::from_repr(3), Some(Reply::Redirect));
+    assert_eq!(Reply::from_repr(9), Some(Reply::Failure));
+    assert_eq!(Reply::from_repr(4), None);
+
+    // The canonical discriminant is still used for encoding
+    assert_eq!(Reply::Ok.as_repr(), 1);
+    assert_eq!(u16::from(Reply::Failure), 500);
+}
+
+#[test]
+fn test_ranges() {
+    assert_eq!(Reply::try_from(200), Ok(Reply::Success));
+    assert_eq!(Reply::try_from(299), Ok(Reply::Success));
+    assert_eq!(Reply::try_from(&399), Ok(Reply::Redirect));
+    assert_eq!(Reply::try_from(400), Ok(Reply::Failure));
+    assert_eq!(Reply::try_from(u16::MAX), Ok(Reply::Failure));
+    assert_eq!(Reply::try_from(199).unwrap_err().0, 199);
+
+    assert_eq!(Reply::Success.as_repr(), 200);
+}
@@ -0,0 +1,50 @@
+use repr_cast::repr_cast;
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq)]
+#[repr_cast(u8)]
+enum Status {
+    #[repr_cast(rename = "pending")]
+    Pending = 0,
+    /// Kept for encoding old records only
+    #[repr_cast(skip)]
+    Legacy = 1,
+    Active = 2,
+    #[repr_cast(hidden, rename = "internal")]
+    Internal = 3,
+}
+
+#[test]
+fn test_skip() {
+    assert_eq!(Status::from_repr(0), Some(Status::Pending));
+    assert_eq!(Status::from_repr(1), None);
+    assert_eq!(Status::try_from(1).unwrap_err().0, 1);
+
+    // Skipped variants still encode
+    assert_eq!(Status::Legacy.as_repr(), 1);
+    assert_eq!(u8::from(Status::Legacy), 1);
+}
+
+#[test]
+fn test_rename() {
+    assert_eq!(Status::Pending.name(), "pending");
+    assert_eq!(Status::Active.name(), "Active");
+    assert_eq!(Status::Internal.name(), "internal");
+
+    const NAME: &str = Status::Legacy.name();
+    assert_eq!(NAME, "Legacy");
+}
+
+#[test]
+fn test_hidden() {
+    assert_eq!(Status::COUNT, 3);
+    assert_eq!(Status::COUNT, Status::VARIANTS.len());
+    assert_eq!(Status::Internal.ordinal(), 3);
+    assert_eq!(Status::from_ordinal(3), Some(Status::Internal));
+    assert_eq!(
+        Status::VARIANTS,
+        &[Status::Pending, Status::Legacy, Status::Active]
+    );
+    let names: Vec<_> = Status::iter().map(Status::name).collect();
+    assert_eq!(names, ["pending", "Legacy", "Active"]);
+}